@@ -0,0 +1,200 @@
+//! Object composition: deriving related schemas such as "create", "update"
+//! and "response" shapes from one base object schema
+//!
+//! The combinators are methods on `Schema`. Builders that cannot call into
+//! Rust directly describe them as a `compose` node instead, which is expanded
+//! with the same methods when the schema JSON is read.
+
+use crate::schema::{Modifiers, ObjectSchema, Schema, SchemaError, UiConfig};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+impl Schema {
+    /// Add fields to an object schema, replacing fields of the same name in place
+    pub fn extend(&self, shape: IndexMap<String, Schema>) -> Result<Schema, SchemaError> {
+        let mut object = self.object("extend")?.clone();
+        object.shape.extend(shape);
+        Ok(Schema::Object(object))
+    }
+
+    /// Combine two object schemas
+    ///
+    /// Fields of `other` replace fields of the same name, and its unknown-key
+    /// policy applies when it sets one. The definitions of both are combined;
+    /// a name defined differently by each is an error. Everything else comes
+    /// from `self`.
+    pub fn merge(&self, other: &Schema) -> Result<Schema, SchemaError> {
+        let mut object = self.object("merge")?.clone();
+        let other = other.object("merge")?;
+        object.shape.extend(other.shape.clone());
+        if other.unknown_keys.is_some() {
+            object.unknown_keys = other.unknown_keys.clone();
+        }
+        add_definitions(&mut object.modifiers.definitions, &other.modifiers.definitions)?;
+        Ok(Schema::Object(object))
+    }
+
+    /// Keep only the named fields of an object schema, in their original order
+    pub fn pick<S: AsRef<str>>(&self, keys: &[S]) -> Result<Schema, SchemaError> {
+        let mut object = self.object("pick")?.clone();
+        object.shape.retain(|key, _| keys.iter().any(|k| k.as_ref() == key));
+        Ok(Schema::Object(object))
+    }
+
+    /// Drop the named fields of an object schema
+    pub fn omit<S: AsRef<str>>(&self, keys: &[S]) -> Result<Schema, SchemaError> {
+        let mut object = self.object("omit")?.clone();
+        object.shape.retain(|key, _| !keys.iter().any(|k| k.as_ref() == key));
+        Ok(Schema::Object(object))
+    }
+
+    /// Make every field of an object schema optional
+    pub fn partial(&self) -> Result<Schema, SchemaError> {
+        let mut object = self.object("partial")?.clone();
+        for field in object.shape.values_mut() {
+            field.modifiers_mut().optional = Some(true);
+        }
+        Ok(Schema::Object(object))
+    }
+
+    /// Make every field of an object schema required
+    ///
+    /// Defaults are kept, so a defaulted field still accepts a missing key.
+    pub fn required(&self) -> Result<Schema, SchemaError> {
+        let mut object = self.object("required")?.clone();
+        for field in object.shape.values_mut() {
+            field.modifiers_mut().optional = None;
+        }
+        Ok(Schema::Object(object))
+    }
+
+    /// Make fields optional at every level of nesting
+    ///
+    /// Descends through object fields, array and record elements, tuple
+    /// positions and intersection options; other nodes are returned unchanged.
+    /// Union options are left alone, since dropping fields there would change
+    /// which option a value matches.
+    pub fn deep_partial(&self) -> Schema {
+        let mut schema = self.clone();
+        match &mut schema {
+            Schema::Object(object) => {
+                for field in object.shape.values_mut() {
+                    *field = field.deep_partial();
+                    field.modifiers_mut().optional = Some(true);
+                }
+            }
+            Schema::Array(array) => *array.items = array.items.deep_partial(),
+            Schema::Record(record) => *record.values = record.values.deep_partial(),
+            Schema::Tuple(tuple) => {
+                for item in &mut tuple.items {
+                    *item = item.deep_partial();
+                }
+                if let Some(rest) = &mut tuple.rest {
+                    **rest = rest.deep_partial();
+                }
+            }
+            Schema::Intersection(intersection) => {
+                for option in &mut intersection.options {
+                    *option = option.deep_partial();
+                }
+            }
+            _ => {}
+        }
+        schema
+    }
+
+    /// The object schema an operation applies to
+    fn object(&self, operation: &'static str) -> Result<&ObjectSchema, SchemaError> {
+        match self {
+            Schema::Object(object) => Ok(object),
+            other => Err(SchemaError::NotAnObject {
+                operation,
+                found: other.type_name(),
+            }),
+        }
+    }
+}
+
+/// Add definitions to a table, rejecting a name that is already defined
+/// as a different schema
+fn add_definitions(
+    table: &mut IndexMap<String, Schema>,
+    added: &IndexMap<String, Schema>,
+) -> Result<(), SchemaError> {
+    for (name, schema) in added {
+        match table.get(name) {
+            Some(existing) if !same_schema(existing, schema) => {
+                return Err(SchemaError::ConflictingDefinition { name: name.clone() });
+            }
+            Some(_) => {}
+            None => {
+                table.insert(name.clone(), schema.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether two schemas have the same JSON form
+fn same_schema(a: &Schema, b: &Schema) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Combinator applied by a `compose` node
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Step {
+    Extend { shape: IndexMap<String, Schema> },
+    Merge { schema: Box<Schema> },
+    Pick { keys: Vec<String> },
+    Omit { keys: Vec<String> },
+    Partial,
+    DeepPartial,
+    Required,
+}
+
+/// `compose` node: a base schema and the combinators applied to it in order
+///
+/// UI configuration and modifiers set on the node itself apply to the result.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Composition {
+    schema: Schema,
+    #[serde(default)]
+    steps: Vec<Step>,
+    ui: Option<UiConfig>,
+    #[serde(flatten)]
+    modifiers: Modifiers,
+}
+
+impl Composition {
+    /// Apply the steps and return the resulting schema
+    pub(crate) fn apply(self) -> Result<Schema, SchemaError> {
+        let mut schema = self.schema;
+        for step in self.steps {
+            schema = match step {
+                Step::Extend { shape } => schema.extend(shape)?,
+                Step::Merge { schema: other } => schema.merge(&other)?,
+                Step::Pick { keys } => schema.pick(&keys)?,
+                Step::Omit { keys } => schema.omit(&keys)?,
+                Step::Partial => schema.partial()?,
+                Step::DeepPartial => schema.deep_partial(),
+                Step::Required => schema.required()?,
+            };
+        }
+
+        if self.ui.is_some() {
+            *schema.ui_mut() = self.ui;
+        }
+        let modifiers = schema.modifiers_mut();
+        modifiers.optional = self.modifiers.optional.or(modifiers.optional);
+        modifiers.nullable = self.modifiers.nullable.or(modifiers.nullable);
+        modifiers.default = self.modifiers.default.or(modifiers.default.take());
+        modifiers.coerce = self.modifiers.coerce.or(modifiers.coerce);
+        add_definitions(&mut modifiers.definitions, &self.modifiers.definitions)?;
+
+        Ok(schema)
+    }
+}
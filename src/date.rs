@@ -0,0 +1,370 @@
+//! Calendar arithmetic for `Schema::Date` and `Schema::DateTime`
+//!
+//! Dates are counted in days and instants in nanoseconds since the Unix
+//! epoch on the proleptic Gregorian calendar. Local times are placed on the
+//! timeline with a fixed `UtcOffset`; there is no time zone database.
+
+use crate::format;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+/// Largest `now` shift in months: the 10 000 years a four-digit year spans
+const MAX_SHIFT_MONTHS: i64 = 12 * 10_000;
+/// Largest `now` shift in days, 10 000 Gregorian years
+const MAX_SHIFT_DAYS: i64 = 3_652_425;
+
+/// Days since 1970-01-01 of a valid civil date
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    // Shift the year to start in March so the leap day falls at its end
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (i64::from(month) + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Civil date of a day count since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+/// Move a date by whole months, clamping the day to the end of the month
+///
+/// `None` if the resulting year does not fit the calendar's `i32` years.
+fn add_months(days: i64, months: i64) -> Option<i64> {
+    let (year, month, day) = civil_from_days(days);
+    let index = (i64::from(year) * 12 + i64::from(month) - 1).checked_add(months)?;
+    let year = i32::try_from(index.div_euclid(12)).ok()?;
+    let month = (index.rem_euclid(12) + 1) as u32;
+    let day = day.min(format::days_in_month(year, month));
+    Some(days_from_civil(year, month, day))
+}
+
+/// Whole years from `birth` to `today`, both as day counts
+pub fn age(birth: i64, today: i64) -> i64 {
+    let (birth_year, birth_month, birth_day) = civil_from_days(birth);
+    let (year, month, day) = civil_from_days(today);
+    let years = i64::from(year - birth_year);
+    if (month, day) < (birth_month, birth_day) {
+        years - 1
+    } else {
+        years
+    }
+}
+
+/// `YYYY-MM-DD` of a day count
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// RFC 3339 UTC timestamp of an instant, with milliseconds when present
+pub fn format_instant(nanos: i128) -> String {
+    let days = nanos.div_euclid(NANOS_PER_DAY);
+    let time = nanos.rem_euclid(NANOS_PER_DAY);
+    let seconds = time / NANOS_PER_SECOND;
+    let millis = time % NANOS_PER_SECOND / 1_000_000;
+    let fraction = if millis > 0 { format!(".{:03}", millis) } else { String::new() };
+    format!(
+        "{}T{:02}:{:02}:{:02}{}Z",
+        format_date(days as i64),
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        fraction
+    )
+}
+
+/// Instant of an epoch millisecond timestamp
+pub fn instant_from_millis(millis: i64) -> i128 {
+    i128::from(millis) * 1_000_000
+}
+
+/// Parse `YYYY-MM-DD` into a day count
+pub fn parse_date(s: &str) -> Option<i64> {
+    format::parse_date(s).map(|(year, month, day)| days_from_civil(year, month, day))
+}
+
+/// Date-time parts: local time in nanoseconds since the epoch and the offset
+/// in minutes, `None` when the input has no offset
+pub fn parse_datetime(s: &str) -> Option<(i128, Option<i32>)> {
+    let (date, time, zone) = format::split_datetime(s)?;
+    let days = parse_date(date)?;
+    let (hour, minute, second, nanos) = format::parse_time(time)?;
+    let offset = match zone {
+        "" => None,
+        zone => Some(format::parse_offset(zone)?),
+    };
+    let seconds = i128::from(hour * 3600 + minute * 60 + second);
+    let local = i128::from(days) * NANOS_PER_DAY + seconds * NANOS_PER_SECOND + i128::from(nanos);
+    Some((local, offset))
+}
+
+/// Fixed offset from UTC used to read local times and to decide what "today" is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UtcOffset {
+    minutes: i32,
+}
+
+impl UtcOffset {
+    pub fn from_minutes(minutes: i32) -> Self {
+        UtcOffset { minutes }
+    }
+
+    fn nanos(self) -> i128 {
+        i128::from(self.minutes) * NANOS_PER_MINUTE
+    }
+
+    /// Instant of a local time at this offset
+    pub fn to_instant(self, local: i128) -> i128 {
+        local - self.nanos()
+    }
+
+    /// Local date of an instant at this offset
+    pub fn date_of(self, instant: i128) -> i64 {
+        (instant + self.nanos()).div_euclid(NANOS_PER_DAY) as i64
+    }
+}
+
+impl Serialize for UtcOffset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.minutes == 0 {
+            return serializer.serialize_str("Z");
+        }
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.unsigned_abs();
+        serializer.serialize_str(&format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60))
+    }
+}
+
+impl<'de> Deserialize<'de> for UtcOffset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        format::parse_offset(&text)
+            .map(UtcOffset::from_minutes)
+            .ok_or_else(|| de::Error::custom(format!("invalid UTC offset '{}', expected Z or ±HH:MM", text)))
+    }
+}
+
+/// Bound for a date or date-time: an ISO 8601 value, or `now` optionally
+/// shifted by a signed ISO 8601 duration such as `now+P1D` or `now-PT2H`
+///
+/// For dates `now` means today at the schema's `utc_offset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateBound {
+    text: String,
+    kind: BoundKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BoundKind {
+    Date(i64),
+    DateTime(i128, Option<i32>),
+    Now(Shift),
+}
+
+/// Signed calendar shift; months and days move the local date, `nanos` the time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Shift {
+    months: i64,
+    days: i64,
+    nanos: i128,
+}
+
+impl DateBound {
+    pub fn parse(text: &str) -> Option<DateBound> {
+        let kind = if let Some(rest) = text.strip_prefix("now") {
+            BoundKind::Now(Shift::parse(rest)?)
+        } else if let Some(days) = parse_date(text) {
+            BoundKind::Date(days)
+        } else {
+            let (local, offset) = parse_datetime(text)?;
+            BoundKind::DateTime(local, offset)
+        };
+        Some(DateBound { text: text.to_string(), kind })
+    }
+
+    /// Bound as a day count; date-times count by their local date
+    ///
+    /// `None` if a shifted `now` leaves the calendar.
+    pub fn resolve_date(&self, now: i128, offset: UtcOffset) -> Option<i64> {
+        match &self.kind {
+            BoundKind::Date(days) => Some(*days),
+            BoundKind::DateTime(local, _) => Some(local.div_euclid(NANOS_PER_DAY) as i64),
+            BoundKind::Now(shift) => {
+                let today = offset.date_of(now);
+                let date = shift.apply(i128::from(today) * NANOS_PER_DAY)?;
+                Some(date.div_euclid(NANOS_PER_DAY) as i64)
+            }
+        }
+    }
+
+    /// Bound as an instant; dates mean their start at `offset`
+    ///
+    /// `None` if a shifted `now` leaves the calendar.
+    pub fn resolve_instant(&self, now: i128, offset: UtcOffset) -> Option<i128> {
+        match &self.kind {
+            BoundKind::Date(days) => Some(offset.to_instant(i128::from(*days) * NANOS_PER_DAY)),
+            BoundKind::DateTime(local, own) => {
+                Some(own.map_or(offset, UtcOffset::from_minutes).to_instant(*local))
+            }
+            BoundKind::Now(shift) => {
+                // Calendar parts move the local date so `now-P1M` respects month lengths
+                Some(offset.to_instant(shift.apply(now + offset.nanos())?))
+            }
+        }
+    }
+}
+
+impl Shift {
+    /// Parse an empty string or a sign followed by an ISO 8601 duration with
+    /// whole-number components
+    ///
+    /// Shifts longer than 10 000 years are rejected, as no four-digit date
+    /// lies that far from any other.
+    fn parse(s: &str) -> Option<Shift> {
+        if s.is_empty() {
+            return Some(Shift::default());
+        }
+        let (sign, rest) = match s.as_bytes()[0] {
+            b'+' => (1, &s[1..]),
+            b'-' => (-1, &s[1..]),
+            _ => return None,
+        };
+        let rest = rest.strip_prefix('P')?;
+        let (date, time) = match rest.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (rest, None),
+        };
+
+        let mut shift = Shift::default();
+        let mut any = false;
+        for (amount, designator) in Self::components(date, "YMWD")? {
+            match designator {
+                'Y' => shift.months = shift.months.checked_add(amount.checked_mul(12)?)?,
+                'M' => shift.months = shift.months.checked_add(amount)?,
+                'W' => shift.days = shift.days.checked_add(amount.checked_mul(7)?)?,
+                _ => shift.days = shift.days.checked_add(amount)?,
+            }
+            any = true;
+        }
+        if let Some(time) = time {
+            let components = Self::components(time, "HMS")?;
+            if components.is_empty() {
+                return None;
+            }
+            for (amount, designator) in components {
+                let unit = match designator {
+                    'H' => 60 * NANOS_PER_MINUTE,
+                    'M' => NANOS_PER_MINUTE,
+                    _ => NANOS_PER_SECOND,
+                };
+                shift.nanos = shift.nanos.checked_add(i128::from(amount).checked_mul(unit)?)?;
+            }
+            any = true;
+        }
+        let max_nanos = i128::from(MAX_SHIFT_DAYS) * NANOS_PER_DAY;
+        if !any
+            || shift.months > MAX_SHIFT_MONTHS
+            || shift.days > MAX_SHIFT_DAYS
+            || shift.nanos > max_nanos
+        {
+            return None;
+        }
+
+        shift.months *= sign;
+        shift.days *= sign;
+        shift.nanos *= i128::from(sign);
+        Some(shift)
+    }
+
+    /// `<digits><designator>` pairs in `designators` order
+    fn components(s: &str, designators: &str) -> Option<Vec<(i64, char)>> {
+        let mut components = Vec::new();
+        let mut remaining = designators;
+        let mut rest = s;
+        while !rest.is_empty() {
+            let end = rest.find(|c: char| !c.is_ascii_digit())?;
+            let designator = rest[end..].chars().next()?;
+            let position = remaining.find(designator)?;
+            let amount = rest[..end].parse::<i64>().ok()?;
+            components.push((amount, designator));
+            remaining = &remaining[position + 1..];
+            rest = &rest[end + 1..];
+        }
+        Some(components)
+    }
+
+    /// Shift a local time in nanoseconds since the epoch
+    fn apply(self, local: i128) -> Option<i128> {
+        let days = i64::try_from(local.div_euclid(NANOS_PER_DAY)).ok()?;
+        let time = local.rem_euclid(NANOS_PER_DAY);
+        let days = add_months(days, self.months)?.checked_add(self.days)?;
+        (i128::from(days) * NANOS_PER_DAY + time).checked_add(self.nanos)
+    }
+}
+
+impl std::fmt::Display for DateBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Serialize for DateBound {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateBound {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        DateBound::parse(&text).ok_or_else(|| {
+            de::Error::custom(format!(
+                "invalid date bound '{}', expected an ISO 8601 date, date-time or now[±duration]",
+                text
+            ))
+        })
+    }
+}
+
+/// Current time in milliseconds since the epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn current_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64)
+}
+
+/// Current time in milliseconds since the epoch
+#[cfg(target_arch = "wasm32")]
+pub fn current_millis() -> i64 {
+    js::now() as i64
+}
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // `SystemTime` is unavailable on wasm32-unknown-unknown
+        #[wasm_bindgen(js_namespace = Date)]
+        pub fn now() -> f64;
+    }
+}
@@ -0,0 +1,187 @@
+//! Exact decimal numbers for `Schema::Decimal` and `Schema::BigInt`
+//!
+//! Values are kept as the digits of their JSON text (serde_json's
+//! `arbitrary_precision` mode) and compared digit by digit, so amounts like
+//! `0.1` and integers beyond 2^53 are checked without rounding through `f64`.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Exact decimal number parsed from JSON number text or a numeric string
+///
+/// Bounds in a schema accept either form and are written back as strings so
+/// no digits are lost on the way to JavaScript.
+#[derive(Debug, Clone)]
+pub struct Numeric {
+    /// Text the number was parsed from
+    source: String,
+    negative: bool,
+    /// Significant digits without leading or trailing zeros; empty for zero
+    digits: String,
+    /// Power of ten the digits are scaled by
+    exponent: i64,
+}
+
+impl Numeric {
+    /// Parse JSON number syntax: `-?digits(.digits)?([eE][+-]?digits)?`
+    ///
+    /// Leading zeros in the integer part are accepted for numeric strings.
+    pub fn parse(text: &str) -> Option<Numeric> {
+        let (negative, rest) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let (mantissa, exp) = match rest.find(['e', 'E']) {
+            Some(at) => (&rest[..at], Some(&rest[at + 1..])),
+            None => (rest, None),
+        };
+        let (int, frac) = match mantissa.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (mantissa, None),
+        };
+
+        let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !all_digits(int) || frac.is_some_and(|f| !all_digits(f)) {
+            return None;
+        }
+        let exp = match exp {
+            Some(exp) => {
+                let unsigned = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+                if !all_digits(unsigned) {
+                    return None;
+                }
+                exp.parse::<i64>().ok()?
+            }
+            None => 0,
+        };
+
+        let frac = frac.unwrap_or("");
+        let joined = format!("{}{}", int, frac);
+        let significant = joined.trim_start_matches('0');
+        let digits = significant.trim_end_matches('0');
+        let trailing = (significant.len() - digits.len()) as i64;
+        let exponent = if digits.is_empty() {
+            0
+        } else {
+            exp.checked_sub(frac.len() as i64)?.checked_add(trailing)?
+        };
+
+        Some(Numeric {
+            source: text.to_string(),
+            negative: negative && !digits.is_empty(),
+            digits: digits.to_string(),
+            exponent,
+        })
+    }
+
+    /// Read a JSON number or numeric string
+    pub fn from_value(value: &Value) -> Option<Numeric> {
+        match value {
+            Value::Number(n) => Numeric::parse(&n.to_string()),
+            Value::String(s) => Numeric::parse(s),
+            _ => None,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.exponent >= 0
+    }
+
+    /// Digits after the decimal point, ignoring trailing zeros
+    pub fn scale(&self) -> u64 {
+        if self.exponent < 0 {
+            self.exponent.unsigned_abs()
+        } else {
+            0
+        }
+    }
+
+    /// Digits before the decimal point, ignoring leading zeros
+    pub fn integer_digits(&self) -> u64 {
+        u64::try_from(self.magnitude().max(0)).unwrap_or(u64::MAX)
+    }
+
+    /// Total digits as counted by SQL `DECIMAL(precision, scale)`
+    pub fn precision(&self) -> u64 {
+        self.integer_digits().saturating_add(self.scale())
+    }
+
+    /// Normalized text that is the same for every spelling of a value,
+    /// e.g. `1e2` for `100`, `1e2` and `100.0`
+    pub fn canonical(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let sign = if self.negative { "-" } else { "" };
+        format!("{}{}e{}", sign, self.digits, self.exponent)
+    }
+
+    /// Position of the most significant digit relative to the decimal point
+    fn magnitude(&self) -> i128 {
+        self.digits.len() as i128 + self.exponent as i128
+    }
+
+    fn cmp_magnitude(&self, other: &Numeric) -> Ordering {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // Without trailing zeros, digit strings of equal magnitude compare lexicographically
+            (false, false) => self
+                .magnitude()
+                .cmp(&other.magnitude())
+                .then_with(|| self.digits.cmp(&other.digits)),
+        }
+    }
+}
+
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Numeric {}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Numeric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+        }
+    }
+}
+
+impl fmt::Display for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Numeric {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Numeric {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Numeric::from_value(&value)
+            .ok_or_else(|| de::Error::custom(format!("expected a decimal number, got {}", value)))
+    }
+}
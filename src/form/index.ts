@@ -134,6 +134,15 @@ export function createForm<T extends Record<string, any>>(
     Record<keyof T, ValidationError | null>
   > {
     const result = await Validator.validate(schema, state.values);
+    return fieldErrorsFrom(result.errors);
+  }
+
+  /**
+   * Map validation errors to the first error of each top-level field
+   */
+  function fieldErrorsFrom(
+    errors: ValidationError[] | undefined,
+  ): Record<keyof T, ValidationError | null> {
     const fieldErrors: Record<keyof T, ValidationError | null> = {
       ...state.errors,
     };
@@ -144,13 +153,11 @@ export function createForm<T extends Record<string, any>>(
     }
 
     // Populate errors from validation result
-    if (!result.success && result.errors) {
-      for (const error of result.errors) {
-        if (error.path.length > 0) {
-          const fieldName = error.path[0] as keyof T;
-          if (fieldName in fieldErrors) {
-            fieldErrors[fieldName] = error;
-          }
+    for (const error of errors || []) {
+      if (error.path.length > 0) {
+        const fieldName = error.path[0] as keyof T;
+        if (fieldName in fieldErrors) {
+          fieldErrors[fieldName] = error;
         }
       }
     }
@@ -220,16 +227,17 @@ export function createForm<T extends Record<string, any>>(
       state.touched[key] = true;
     }
 
-    // Validate entire form
-    state.errors = await validateForm();
+    // Parse entire form, so onSubmit receives the cleaned output
+    const result = await Validator.parseResult(schema, state.values);
+    state.errors = fieldErrorsFrom(result.errors);
     updateValidity();
     state.isSubmitting = true;
     notify();
 
     // If valid, call onSubmit
-    if (state.isValid) {
+    if (state.isValid && result.success) {
       try {
-        await onSubmit(state.values);
+        await onSubmit(result.data as T);
       } catch (error) {
         console.error("Form submission error:", error);
       }
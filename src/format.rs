@@ -0,0 +1,561 @@
+use crate::schema::{DateTimeOffset, StringMessages, StringSchema};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use url::{Host, Url};
+
+/// Schemes `url` accepts when the schema does not list its own: the WHATWG
+/// special schemes that address a network host
+const DEFAULT_SCHEMES: [&str; 5] = ["http", "https", "ws", "wss", "ftp"];
+
+/// RFC 5321 limits on an email address and its local part; the address
+/// limit can be changed with `max_length`
+const MAX_EMAIL_LENGTH: usize = 254;
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// Check an email address, returning the reason it is rejected
+///
+/// Accepts an RFC 5322 `addr-spec`: a dot-atom or quoted local part and a
+/// domain name or bracketed IP literal.
+pub fn check_email(s: &str, schema: &StringSchema) -> Result<(), &'static str> {
+    if s.len() > schema.max_length.unwrap_or(MAX_EMAIL_LENGTH) {
+        return Err("too_long");
+    }
+
+    let (local, domain) = s.rsplit_once('@').ok_or("syntax")?;
+    if local.len() > MAX_LOCAL_PART_LENGTH {
+        return Err("local_part_too_long");
+    }
+    if !is_local_part(local, schema.allow_idn != Some(false)) {
+        return Err("syntax");
+    }
+
+    if let Some(literal) = domain.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+        let is_ip = literal.parse::<Ipv4Addr>().is_ok()
+            || literal
+                .strip_prefix("IPv6:")
+                .is_some_and(|v6| v6.parse::<Ipv6Addr>().is_ok());
+        return match (is_ip, schema.allow_ip) {
+            (false, _) => Err("syntax"),
+            (true, Some(false)) => Err("ip_host"),
+            (true, _) => Ok(()),
+        };
+    }
+
+    check_domain(domain, schema, true)
+}
+
+/// Dot-atom or quoted-string local part; `unicode` allows RFC 6531 UTF-8
+fn is_local_part(local: &str, unicode: bool) -> bool {
+    if let Some(quoted) = local.strip_prefix('"').and_then(|l| l.strip_suffix('"')) {
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            let ok = match c {
+                '\\' => chars.next().is_some_and(|escaped| escaped == ' ' || escaped.is_ascii_graphic()),
+                '"' => false,
+                c => c == ' ' || c.is_ascii_graphic() || (unicode && !c.is_ascii()),
+            };
+            if !ok {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    local.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || "!#$%&'*+-/=?^_`{|}~".contains(c)
+                    || (unicode && !c.is_ascii())
+            })
+    })
+}
+
+/// Check a URL, returning the reason it is rejected
+///
+/// Parses with the WHATWG URL Standard, then applies the schema's scheme and
+/// host options.
+pub fn check_url(s: &str, schema: &StringSchema) -> Result<(), &'static str> {
+    if schema.max_length.is_some_and(|max| s.len() > max) {
+        return Err("too_long");
+    }
+
+    let url = Url::parse(s).map_err(|_| "syntax")?;
+
+    let scheme_allowed = match &schema.schemes {
+        Some(schemes) => schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(url.scheme())),
+        None => DEFAULT_SCHEMES.contains(&url.scheme()),
+    };
+    if !scheme_allowed {
+        return Err("scheme");
+    }
+
+    match url.host() {
+        // The parser has already converted IDNs to punycode, which
+        // `check_domain` recognizes by its `xn--` labels
+        Some(Host::Domain(domain)) if url.is_special() => check_domain(domain, schema, false),
+        Some(Host::Ipv4(_) | Host::Ipv6(_)) if schema.allow_ip == Some(false) => Err("ip_host"),
+        _ => Ok(()),
+    }
+}
+
+/// Check a domain name against the IDN and TLD options
+///
+/// `require_tld` applies when the schema leaves it unset.
+fn check_domain(domain: &str, schema: &StringSchema, require_tld: bool) -> Result<(), &'static str> {
+    let allow_idn = schema.allow_idn != Some(false);
+    if !domain.is_ascii() && !allow_idn {
+        return Err("idn");
+    }
+
+    let ascii = idna::domain_to_ascii(domain).map_err(|_| "syntax")?;
+    let ascii = ascii.strip_suffix('.').unwrap_or(&ascii);
+    if !allow_idn && ascii.split('.').any(|label| label.starts_with("xn--")) {
+        return Err("idn");
+    }
+    if !is_hostname(ascii) {
+        return Err("syntax");
+    }
+
+    let tld = ascii.rsplit_once('.').map(|(_, tld)| tld);
+    let has_tld = tld.is_some_and(|tld| {
+        tld.len() >= 2 && (tld.bytes().all(|b| b.is_ascii_alphabetic()) || tld.starts_with("xn--"))
+    });
+    if schema.require_tld.unwrap_or(require_tld) && !has_tld {
+        return Err("missing_tld");
+    }
+
+    Ok(())
+}
+
+/// Built-in string formats enabled by boolean flags on `StringSchema`
+///
+/// Each reports a `string.<name>` error code and has a `StringMessages` entry
+/// of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Uuid,
+    Ulid,
+    Cuid,
+    Ipv4,
+    Ipv6,
+    Cidr,
+    Hostname,
+    Date,
+    Time,
+    DateTime,
+    Duration,
+    Base64,
+    Hex,
+    Semver,
+    Jwt,
+}
+
+impl Format {
+    pub const ALL: [Format; 15] = [
+        Format::Uuid,
+        Format::Ulid,
+        Format::Cuid,
+        Format::Ipv4,
+        Format::Ipv6,
+        Format::Cidr,
+        Format::Hostname,
+        Format::Date,
+        Format::Time,
+        Format::DateTime,
+        Format::Duration,
+        Format::Base64,
+        Format::Hex,
+        Format::Semver,
+        Format::Jwt,
+    ];
+
+    /// Name used in the schema flag, the error code and `StringMessages`
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Uuid => "uuid",
+            Format::Ulid => "ulid",
+            Format::Cuid => "cuid",
+            Format::Ipv4 => "ipv4",
+            Format::Ipv6 => "ipv6",
+            Format::Cidr => "cidr",
+            Format::Hostname => "hostname",
+            Format::Date => "date",
+            Format::Time => "time",
+            Format::DateTime => "datetime",
+            Format::Duration => "duration",
+            Format::Base64 => "base64",
+            Format::Hex => "hex",
+            Format::Semver => "semver",
+            Format::Jwt => "jwt",
+        }
+    }
+
+    /// Whether a schema enables this format
+    pub fn is_enabled(self, schema: &StringSchema) -> bool {
+        let flag = match self {
+            Format::Uuid => schema.uuid,
+            Format::Ulid => schema.ulid,
+            Format::Cuid => schema.cuid,
+            Format::Ipv4 => schema.ipv4,
+            Format::Ipv6 => schema.ipv6,
+            Format::Cidr => schema.cidr,
+            Format::Hostname => schema.hostname,
+            Format::Date => schema.date,
+            Format::Time => schema.time,
+            Format::DateTime => schema.datetime,
+            Format::Duration => schema.duration,
+            Format::Base64 => schema.base64,
+            Format::Hex => schema.hex,
+            Format::Semver => schema.semver,
+            Format::Jwt => schema.jwt,
+        };
+        flag == Some(true)
+    }
+
+    /// Custom message for this format
+    pub fn message(self, messages: &StringMessages) -> Option<&str> {
+        let message = match self {
+            Format::Uuid => &messages.uuid,
+            Format::Ulid => &messages.ulid,
+            Format::Cuid => &messages.cuid,
+            Format::Ipv4 => &messages.ipv4,
+            Format::Ipv6 => &messages.ipv6,
+            Format::Cidr => &messages.cidr,
+            Format::Hostname => &messages.hostname,
+            Format::Date => &messages.date,
+            Format::Time => &messages.time,
+            Format::DateTime => &messages.datetime,
+            Format::Duration => &messages.duration,
+            Format::Base64 => &messages.base64,
+            Format::Hex => &messages.hex,
+            Format::Semver => &messages.semver,
+            Format::Jwt => &messages.jwt,
+        };
+        message.as_deref()
+    }
+
+    /// Check a string against this format
+    pub fn matches(self, s: &str, schema: &StringSchema) -> bool {
+        match self {
+            Format::Uuid => is_uuid(s),
+            Format::Ulid => is_ulid(s),
+            Format::Cuid => is_cuid(s),
+            Format::Ipv4 => s.parse::<Ipv4Addr>().is_ok(),
+            Format::Ipv6 => s.parse::<Ipv6Addr>().is_ok(),
+            Format::Cidr => is_cidr(s),
+            Format::Hostname => is_hostname(s),
+            Format::Date => is_date(s),
+            Format::Time => is_time(s),
+            Format::DateTime => is_datetime(s, schema.offset.unwrap_or_default()),
+            Format::Duration => is_duration(s),
+            Format::Base64 => is_base64(s),
+            Format::Hex => !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit()),
+            Format::Semver => is_semver(s),
+            Format::Jwt => is_jwt(s),
+        }
+    }
+}
+
+/// RFC 9562 UUID in 8-4-4-4-12 hex form, including the nil and max UUIDs
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    let shape_ok = groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit())
+        });
+    if !shape_ok {
+        return false;
+    }
+
+    if s.bytes().all(|b| b == b'0' || b == b'-') || s.bytes().all(|b| matches!(b, b'f' | b'F' | b'-')) {
+        return true;
+    }
+
+    let version = groups[2].as_bytes()[0];
+    let variant = groups[3].as_bytes()[0].to_ascii_lowercase();
+    (b'1'..=b'8').contains(&version) && matches!(variant, b'8' | b'9' | b'a' | b'b')
+}
+
+/// 26-character Crockford base32 ULID whose timestamp fits in 48 bits
+fn is_ulid(s: &str) -> bool {
+    s.len() == 26
+        && s.as_bytes()[0] <= b'7'
+        && s.bytes().all(|b| {
+            b.is_ascii_digit()
+                || (b.is_ascii_alphabetic() && !matches!(b.to_ascii_uppercase(), b'I' | b'L' | b'O' | b'U'))
+        })
+}
+
+/// CUID: a lowercase `c` followed by at least 8 base36 characters
+fn is_cuid(s: &str) -> bool {
+    s.len() >= 9
+        && s.starts_with('c')
+        && s.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+/// IPv4 or IPv6 address with a `/prefix` length
+fn is_cidr(s: &str) -> bool {
+    let Some((address, prefix)) = s.split_once('/') else {
+        return false;
+    };
+    if prefix.is_empty() || prefix.len() > 3 || (prefix.len() > 1 && prefix.starts_with('0')) {
+        return false;
+    }
+    let Ok(prefix) = prefix.parse::<u8>() else {
+        return false;
+    };
+
+    if address.parse::<Ipv4Addr>().is_ok() {
+        prefix <= 32
+    } else {
+        address.parse::<Ipv6Addr>().is_ok() && prefix <= 128
+    }
+}
+
+/// RFC 1123 hostname, optionally with a trailing dot
+fn is_hostname(s: &str) -> bool {
+    let s = s.strip_suffix('.').unwrap_or(s);
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// Calendar date in `YYYY-MM-DD` form
+fn is_date(s: &str) -> bool {
+    parse_date(s).is_some()
+}
+
+/// Year, month and day of a valid `YYYY-MM-DD` date
+pub fn parse_date(s: &str) -> Option<(i32, u32, u32)> {
+    let b = s.as_bytes();
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+
+    let year = digits(&s[0..4])? as i32;
+    let month = digits(&s[5..7])?;
+    let day = digits(&s[8..10])?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some((year, month, day))
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Time of day as `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fraction`
+fn is_time(s: &str) -> bool {
+    parse_time(s).is_some()
+}
+
+/// Hour, minute, second and nanosecond of a valid time of day
+pub fn parse_time(s: &str) -> Option<(u32, u32, u32, u32)> {
+    let b = s.as_bytes();
+    if !s.is_ascii() || b.len() < 5 || b[2] != b':' {
+        return None;
+    }
+    let hour = digits(&s[0..2])?;
+    let minute = digits(&s[3..5])?;
+    let (second, nanos) = match &s[5..] {
+        "" => (0, 0),
+        rest => {
+            let rest = rest.strip_prefix(':')?;
+            if rest.len() < 2 {
+                return None;
+            }
+            let second = digits(&rest[0..2])?;
+            let nanos = match &rest[2..] {
+                "" => 0,
+                fraction => {
+                    let fraction = fraction.strip_prefix('.')?;
+                    if fraction.is_empty() || fraction.len() > 9 {
+                        return None;
+                    }
+                    digits(fraction)? * 10u32.pow(9 - fraction.len() as u32)
+                }
+            };
+            (second, nanos)
+        }
+    };
+
+    (hour < 24 && minute < 60 && second < 60).then_some((hour, minute, second, nanos))
+}
+
+/// RFC 3339 date-time, `YYYY-MM-DDTHH:MM[:SS[.fraction]]` with an offset
+/// accepted according to `offset`
+fn is_datetime(s: &str, offset: DateTimeOffset) -> bool {
+    split_datetime(s).is_some_and(|(date, time, zone)| {
+        is_date(date) && is_time(time) && offset_allowed(zone, offset)
+    })
+}
+
+/// Split a date-time into its date, time and (possibly empty) offset parts
+pub fn split_datetime(s: &str) -> Option<(&str, &str, &str)> {
+    let (date, rest) = s.split_at_checked(10)?;
+    let rest = rest.strip_prefix(['T', 't'])?;
+    let zone_start = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+    let (time, zone) = rest.split_at(zone_start);
+    Some((date, time, zone))
+}
+
+/// Offset in minutes east of UTC for `Z` or `±HH:MM`
+pub fn parse_offset(zone: &str) -> Option<i32> {
+    if zone.eq_ignore_ascii_case("z") {
+        return Some(0);
+    }
+
+    let (sign, rest) = match zone.as_bytes().first()? {
+        b'+' => (1, &zone[1..]),
+        b'-' => (-1, &zone[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (digits(hours)?, digits(minutes)?);
+    (hours < 24 && minutes < 60).then_some(sign * (hours * 60 + minutes) as i32)
+}
+
+/// Whether an offset (empty when absent) is allowed by the policy
+pub fn offset_allowed(zone: &str, policy: DateTimeOffset) -> bool {
+    match policy {
+        DateTimeOffset::Any => parse_offset(zone).is_some(),
+        DateTimeOffset::Utc => zone.eq_ignore_ascii_case("z"),
+        DateTimeOffset::Local => zone.is_empty(),
+        DateTimeOffset::Optional => zone.is_empty() || parse_offset(zone).is_some(),
+    }
+}
+
+/// ISO 8601 duration such as `P1Y2M3DT4H5M6.5S` or `P2W`
+fn is_duration(s: &str) -> bool {
+    let Some(rest) = s.strip_prefix('P') else {
+        return false;
+    };
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+
+    let date_ok = components(date, "YMWD");
+    let time_ok = time.map(|time| components(time, "HMS"));
+
+    match (date_ok, time_ok) {
+        (Some(date_count), None) => date_count > 0,
+        // A `T` must be followed by at least one time component
+        (Some(_), Some(Some(time_count))) => time_count > 0,
+        _ => false,
+    }
+}
+
+/// Count `<number><designator>` components appearing in `designators` order
+fn components(s: &str, designators: &str) -> Option<usize> {
+    let mut count = 0;
+    let mut remaining = designators;
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let (number, tail) = rest.split_at(end);
+        let designator = tail.chars().next()?;
+
+        let digits_ok = number
+            .split_once(['.', ','])
+            .map_or(!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()), |(int, frac)| {
+                !int.is_empty()
+                    && !frac.is_empty()
+                    && int.bytes().all(|b| b.is_ascii_digit())
+                    && frac.bytes().all(|b| b.is_ascii_digit())
+            });
+        let position = remaining.find(designator)?;
+        if !digits_ok {
+            return None;
+        }
+
+        remaining = &remaining[position + 1..];
+        rest = &tail[1..];
+        count += 1;
+    }
+
+    Some(count)
+}
+
+/// Standard base64 with `=` padding to a multiple of 4 characters
+fn is_base64(s: &str) -> bool {
+    if !s.len().is_multiple_of(4) {
+        return false;
+    }
+    let data = s.trim_end_matches('=');
+    s.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+/// Semantic Versioning 2.0.0 version, e.g. `1.2.3-rc.1+build.5`
+fn is_semver(s: &str) -> bool {
+    let (version, build) = match s.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (s, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let numeric = |part: &str| {
+        !part.is_empty()
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && (part == "0" || !part.starts_with('0'))
+    };
+    let identifier = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+
+    let core: Vec<&str> = core.split('.').collect();
+    core.len() == 3
+        && core.iter().all(|part| numeric(part))
+        && pre.is_none_or(|pre| {
+            pre.split('.').all(|part| {
+                identifier(part) && (!part.bytes().all(|b| b.is_ascii_digit()) || numeric(part))
+            })
+        })
+        && build.is_none_or(|build| build.split('.').all(identifier))
+}
+
+/// JSON Web Token shape: three base64url segments, the last may be empty
+fn is_jwt(s: &str) -> bool {
+    let base64url = |part: &str| {
+        part.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    };
+    let parts: Vec<&str> = s.split('.').collect();
+
+    parts.len() == 3
+        && !parts[0].is_empty()
+        && !parts[1].is_empty()
+        && parts.iter().all(|part| base64url(part))
+}
+
+/// Parse a run of ASCII digits
+fn digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
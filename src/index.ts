@@ -8,18 +8,57 @@ export { r, type Infer, type UiConfig } from "./schema";
 export type {
   Schema,
   StringSchema,
+  Normalization,
+  LengthUnit,
+  DateTimeOffset,
+  Charset,
+  CharClass,
   NumberSchema,
+  DecimalSchema,
+  BigIntSchema,
+  DateSchema,
+  DateTimeSchema,
+  DateBound,
   BooleanSchema,
   ObjectSchema,
+  UnknownKeys,
+  ArraySchema,
+  RecordSchema,
+  TupleSchema,
+  EnumSchema,
+  LiteralSchema,
+  UnionSchema,
+  DiscriminatedUnionSchema,
+  IntersectionSchema,
+  ComposeSchema,
+  CompositionStep,
+  RefSchema,
   ValidationError,
   ValidationResult,
+  ParseResult,
 } from "./schema/types";
 export {
   ZString,
   ZNumber,
+  ZDecimal,
+  ZBigInt,
+  ZDate,
+  ZDateTime,
   ZBoolean,
   ZObject,
+  ZArray,
+  ZRecord,
+  ZTuple,
+  ZEnum,
+  ZLiteral,
+  ZUnion,
+  ZDiscriminatedUnion,
+  ZIntersection,
+  ZComposed,
+  ObjectBuilder,
+  ZRef,
   SchemaBuilder,
+  type HostOptions,
 } from "./schema/builders";
 
 // Validator
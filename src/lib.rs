@@ -1,7 +1,27 @@
+mod compose;
+mod date;
+mod decimal;
+mod format;
+mod locale;
+mod lookup;
+mod pattern;
 mod schema;
 mod validator;
 mod wasm;
 
-pub use schema::{Schema, ValidationError, ValidationResult, UiConfig};
-pub use validator::Validator;
-pub use wasm::WasmValidator;
+pub use schema::{
+    ArrayMessages, ArraySchema, BigIntMessages, BigIntSchema, BooleanMessages, BooleanSchema,
+    CharClass, Charset, DateMessages, DateSchema, DateTimeOffset, DateTimeSchema, DecimalMessages,
+    DecimalSchema, DiscriminatedUnionMessages, DiscriminatedUnionSchema, EnumMessages, EnumSchema,
+    IntersectionMessages, IntersectionSchema, LengthUnit, LiteralMessages, LiteralSchema, Modifiers,
+    Normalization, NumberMessages, NumberSchema, ObjectMessages, ObjectSchema, RecordMessages,
+    RecordSchema, RefSchema, Schema, SchemaError, StringMessages, StringSchema, TupleMessages,
+    TupleSchema, UiConfig, UnionMessages, UnionSchema, UnknownKeys, ValidationError,
+    ValidationResult,
+};
+pub use date::{DateBound, UtcOffset};
+pub use decimal::Numeric;
+pub use indexmap::IndexMap;
+pub use locale::{register_catalog, Catalog};
+pub use validator::{ValidationOptions, Validator};
+pub use wasm::{CompiledSchema, WasmValidator};
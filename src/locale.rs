@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Locale used when none is selected or a message is missing from a catalog
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Built-in catalogs, in the same JSON format accepted by `Catalog::from_json`
+const BUILT_IN: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.json")),
+    ("de", include_str!("locales/de.json")),
+    ("fr", include_str!("locales/fr.json")),
+    ("ja", include_str!("locales/ja.json")),
+];
+
+/// Default message templates keyed by error code
+///
+/// Templates may use the same `{placeholder}`s as custom messages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Parse a catalog from a JSON object of `code -> template`
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Template for an error code
+    pub fn get(&self, code: &str) -> Option<&str> {
+        self.messages.get(code).map(String::as_str)
+    }
+
+    /// Add or replace the template for an error code
+    pub fn insert(&mut self, code: impl Into<String>, template: impl Into<String>) {
+        self.messages.insert(code.into(), template.into());
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Catalog>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Catalog>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let catalogs = BUILT_IN
+            .iter()
+            .map(|(locale, json)| {
+                let catalog = Catalog::from_json(json).expect("built-in catalog is valid JSON");
+                (locale.to_string(), catalog)
+            })
+            .collect();
+        RwLock::new(catalogs)
+    })
+}
+
+/// Register a catalog for a locale
+///
+/// Templates are merged into any catalog already registered for the locale,
+/// so a partial catalog can override selected built-in messages.
+pub fn register_catalog(locale: &str, catalog: Catalog) {
+    let mut catalogs = registry().write().unwrap_or_else(|e| e.into_inner());
+    catalogs
+        .entry(locale.to_string())
+        .or_default()
+        .messages
+        .extend(catalog.messages);
+}
+
+/// Default template for an error code in a locale
+///
+/// Falls back from a regional locale (`de-CH`) to its language (`de`), then
+/// to English, then to the code itself.
+pub fn template(locale: &str, code: &str) -> String {
+    let catalogs = registry().read().unwrap_or_else(|e| e.into_inner());
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+
+    [locale, language, DEFAULT_LOCALE]
+        .iter()
+        .find_map(|candidate| catalogs.get(*candidate).and_then(|c| c.get(code)))
+        .unwrap_or(code)
+        .to_string()
+}
@@ -0,0 +1,91 @@
+//! Lookup tables precomputed for a schema that is validated many times
+//!
+//! Built once by `CompiledSchema`; the stateless entry points validate
+//! without them and find the same answers by scanning the schema.
+
+use crate::schema::{DiscriminatedUnionSchema, Schema};
+use crate::validator::Validator;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// Discriminator tables and resolved references of one schema tree
+///
+/// Discriminated unions are keyed by node address, so the tables are only
+/// valid for the exact tree they were built from, which must not move or
+/// change afterwards.
+#[derive(Debug, Default)]
+pub(crate) struct Lookups {
+    /// Option index for each tag value, per discriminated union node
+    tags: HashMap<usize, HashMap<String, usize>>,
+    /// Definition a name finally refers to, past references to references
+    targets: HashMap<String, String>,
+}
+
+impl Lookups {
+    /// Build the tables for a root schema and its definitions
+    pub(crate) fn build(root: &Schema) -> Self {
+        let definitions = &root.modifiers().definitions;
+        let mut lookups = Lookups::default();
+        lookups.visit(root, definitions);
+
+        for name in definitions.keys() {
+            let mut current = name;
+            // A chain longer than the table revisits a name, so it never ends
+            for _ in 0..=definitions.len() {
+                match &definitions[current] {
+                    Schema::Ref(reference) if definitions.contains_key(&reference.name) => {
+                        current = &reference.name;
+                    }
+                    Schema::Ref(_) => break,
+                    _ => {
+                        lookups.targets.insert(name.clone(), current.clone());
+                        break;
+                    }
+                }
+            }
+        }
+
+        lookups
+    }
+
+    fn visit(&mut self, schema: &Schema, definitions: &IndexMap<String, Schema>) {
+        if let Schema::DiscriminatedUnion(union) = schema {
+            let mut tags = HashMap::new();
+            for (index, option) in union.options.iter().enumerate() {
+                for tag in Validator::discriminator_values(definitions, option, &union.discriminator) {
+                    // The first option accepting a tag wins, as when scanning
+                    tags.entry(Validator::canonical_key(tag)).or_insert(index);
+                }
+            }
+            self.tags.insert(Self::address(union), tags);
+        }
+
+        for child in schema.children() {
+            self.visit(child, definitions);
+        }
+    }
+
+    /// Option of a discriminated union for a tag value, `Some(None)` when no
+    /// option accepts it, or `None` if the union is not part of the tree
+    pub(crate) fn option<'a>(
+        &self,
+        union: &'a DiscriminatedUnionSchema,
+        tag: &serde_json::Value,
+    ) -> Option<Option<&'a Schema>> {
+        let tags = self.tags.get(&Self::address(union))?;
+        Some(tags.get(&Validator::canonical_key(tag)).map(|&index| &union.options[index]))
+    }
+
+    /// Definition a name resolves to once references to references are followed
+    pub(crate) fn target<'a>(
+        &self,
+        definitions: &'a IndexMap<String, Schema>,
+        name: &str,
+    ) -> Option<&'a Schema> {
+        self.targets.get(name).and_then(|target| definitions.get(target))
+    }
+
+    fn address(union: &DiscriminatedUnionSchema) -> usize {
+        union as *const DiscriminatedUnionSchema as usize
+    }
+}
@@ -0,0 +1,175 @@
+use crate::schema::{Schema, SchemaError};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[cfg(feature = "regex")]
+type Compiled = regex::Regex;
+
+/// Without the `regex` feature a pattern is matched as a plain substring
+#[cfg(not(feature = "regex"))]
+type Compiled = String;
+
+/// A compiled pattern, or the reason it failed to compile
+type Entry = Result<Rc<Compiled>, String>;
+
+/// Cache of compiled `pattern` regexes keyed by source and flags
+///
+/// A cache belongs to one `CompiledSchema`, or to a single call of the
+/// stateless entry points, so it only ever holds the patterns of one schema.
+/// Compilation failures are cached as well so an invalid pattern is only
+/// compiled once per cache.
+#[derive(Default)]
+pub struct PatternCache {
+    compiled: RefCell<HashMap<(String, String), Entry>>,
+}
+
+impl PatternCache {
+    /// Compile every pattern in a schema ahead of validation
+    ///
+    /// Definitions are compiled once each and every reference is checked to
+    /// resolve, so a recursive schema is walked without following its cycles.
+    pub fn compile_schema(&self, schema: &Schema) -> Result<(), SchemaError> {
+        let definitions = &schema.modifiers().definitions;
+        self.compile_at(schema, definitions, &mut Vec::new())?;
+
+        for (name, definition) in definitions {
+            let mut path = vec!["definitions".to_string(), name.clone()];
+            self.compile_at(definition, definitions, &mut path)?;
+        }
+
+        Ok(())
+    }
+
+    fn compile_at(
+        &self,
+        schema: &Schema,
+        definitions: &IndexMap<String, Schema>,
+        path: &mut Vec<String>,
+    ) -> Result<(), SchemaError> {
+        match schema {
+            Schema::String(string) => {
+                if let Some(pattern) = &string.pattern {
+                    self.get(pattern, string.flags.as_deref()).map_err(|reason| {
+                        SchemaError::InvalidPattern {
+                            path: path.clone(),
+                            pattern: pattern.clone(),
+                            reason,
+                        }
+                    })?;
+                }
+            }
+            Schema::Object(object) => {
+                for (key, field) in &object.shape {
+                    path.push(key.clone());
+                    self.compile_at(field, definitions, path)?;
+                    path.pop();
+                }
+                if let Some(catchall) = object.catchall() {
+                    self.compile_at(catchall, definitions, path)?;
+                }
+            }
+            Schema::Array(array) => self.compile_at(&array.items, definitions, path)?,
+            Schema::Record(record) => {
+                if let Some(keys) = &record.keys {
+                    self.compile_at(keys, definitions, path)?;
+                }
+                self.compile_at(&record.values, definitions, path)?;
+            }
+            Schema::Tuple(tuple) => {
+                for (index, item) in tuple.items.iter().enumerate() {
+                    path.push(index.to_string());
+                    self.compile_at(item, definitions, path)?;
+                    path.pop();
+                }
+                if let Some(rest) = &tuple.rest {
+                    self.compile_at(rest, definitions, path)?;
+                }
+            }
+            Schema::Union(union) => {
+                for option in &union.options {
+                    self.compile_at(option, definitions, path)?;
+                }
+            }
+            Schema::DiscriminatedUnion(union) => {
+                for option in &union.options {
+                    self.compile_at(option, definitions, path)?;
+                }
+            }
+            Schema::Intersection(intersection) => {
+                for option in &intersection.options {
+                    self.compile_at(option, definitions, path)?;
+                }
+            }
+            Schema::Ref(reference) => {
+                if schema.resolve(definitions).is_none() {
+                    let path = path.clone();
+                    let name = reference.name.clone();
+                    return Err(if definitions.contains_key(&name) {
+                        SchemaError::CircularReference { path, name }
+                    } else {
+                        SchemaError::UnknownReference { path, name }
+                    });
+                }
+            }
+            Schema::Number(_)
+            | Schema::Decimal(_)
+            | Schema::BigInt(_)
+            | Schema::Date(_)
+            | Schema::DateTime(_)
+            | Schema::Boolean(_)
+            | Schema::Enum(_)
+            | Schema::Literal(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Test a string against a pattern
+    pub fn is_match(&self, pattern: &str, flags: Option<&str>, s: &str) -> Result<bool, String> {
+        let compiled = self.get(pattern, flags)?;
+
+        #[cfg(feature = "regex")]
+        return Ok(compiled.is_match(s));
+
+        #[cfg(not(feature = "regex"))]
+        return Ok(s.contains(compiled.as_str()));
+    }
+
+    fn get(&self, pattern: &str, flags: Option<&str>) -> Entry {
+        let key = (pattern.to_string(), flags.unwrap_or_default().to_string());
+
+        if let Some(entry) = self.compiled.borrow().get(&key) {
+            return entry.clone();
+        }
+
+        let entry = Self::build(pattern, flags).map(Rc::new);
+        self.compiled.borrow_mut().insert(key, entry.clone());
+        entry
+    }
+
+    #[cfg(feature = "regex")]
+    fn build(pattern: &str, flags: Option<&str>) -> Result<Compiled, String> {
+        let mut builder = regex::RegexBuilder::new(pattern);
+
+        // Accept JavaScript-style flags; `g`, `y`, `u` and `d` have no effect on a single match
+        for flag in flags.unwrap_or_default().chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'x' => builder.ignore_whitespace(true),
+                'g' | 'y' | 'u' | 'd' => &mut builder,
+                other => return Err(format!("unsupported flag '{}'", other)),
+            };
+        }
+
+        builder.build().map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn build(pattern: &str, _flags: Option<&str>) -> Result<Compiled, String> {
+        Ok(pattern.to_string())
+    }
+}
@@ -1,57 +1,824 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::compose::Composition;
+use crate::date::{DateBound, UtcOffset};
+use crate::decimal::Numeric;
+use indexmap::IndexMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Schema AST representing validation rules
+// Nodes are built once and kept, so variants are stored inline for direct construction
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "lowercase")]
+#[serde(remote = "Self", tag = "type", rename_all = "lowercase")]
 pub enum Schema {
-    String {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        min: Option<usize>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        max: Option<usize>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        email: Option<bool>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        url: Option<bool>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        pattern: Option<String>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        ui: Option<UiConfig>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        messages: Option<StringMessages>,
-    },
-    Number {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        min: Option<f64>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        max: Option<f64>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        integer: Option<bool>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        positive: Option<bool>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        ui: Option<UiConfig>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        messages: Option<NumberMessages>,
-    },
-    Boolean {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        ui: Option<UiConfig>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        messages: Option<BooleanMessages>,
-    },
-    Object {
-        shape: HashMap<String, Schema>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        ui: Option<UiConfig>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        messages: Option<ObjectMessages>,
-    },
+    String(StringSchema),
+    Number(NumberSchema),
+    Decimal(DecimalSchema),
+    BigInt(BigIntSchema),
+    Date(DateSchema),
+    DateTime(DateTimeSchema),
+    Boolean(BooleanSchema),
+    Object(ObjectSchema),
+    Array(ArraySchema),
+    Record(RecordSchema),
+    Tuple(TupleSchema),
+    Enum(EnumSchema),
+    Literal(LiteralSchema),
+    Union(UnionSchema),
+    #[serde(rename = "discriminated_union")]
+    DiscriminatedUnion(DiscriminatedUnionSchema),
+    Intersection(IntersectionSchema),
+    Ref(RefSchema),
 }
 
-/// Custom error messages for string validation
+impl Serialize for Schema {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Schema::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Tagged enums buffer their content, which loses arbitrary-precision
+        // numbers; reading a `Value` first keeps them intact
+        let value = Value::deserialize(deserializer)?;
+        // Composition nodes are expanded on read and never appear in the tree
+        let schema = if value.get("type").and_then(Value::as_str) == Some("compose") {
+            Composition::deserialize(value)
+                .map_err(de::Error::custom)?
+                .apply()
+                .map_err(de::Error::custom)?
+        } else {
+            Schema::deserialize(value).map_err(de::Error::custom)?
+        };
+        // Every node checks its children, so this rejects definitions anywhere below the root
+        if schema.children().any(|child| !child.modifiers().definitions.is_empty()) {
+            return Err(de::Error::custom("`definitions` are only allowed on the root schema"));
+        }
+        Ok(schema)
+    }
+}
+
+impl Schema {
+    /// Modifiers shared by every schema node
+    pub fn modifiers(&self) -> &Modifiers {
+        match self {
+            Schema::String(s) => &s.modifiers,
+            Schema::Number(s) => &s.modifiers,
+            Schema::Decimal(s) => &s.modifiers,
+            Schema::BigInt(s) => &s.modifiers,
+            Schema::Date(s) => &s.modifiers,
+            Schema::DateTime(s) => &s.modifiers,
+            Schema::Boolean(s) => &s.modifiers,
+            Schema::Object(s) => &s.modifiers,
+            Schema::Array(s) => &s.modifiers,
+            Schema::Record(s) => &s.modifiers,
+            Schema::Tuple(s) => &s.modifiers,
+            Schema::Enum(s) => &s.modifiers,
+            Schema::Literal(s) => &s.modifiers,
+            Schema::Union(s) => &s.modifiers,
+            Schema::DiscriminatedUnion(s) => &s.modifiers,
+            Schema::Intersection(s) => &s.modifiers,
+            Schema::Ref(s) => &s.modifiers,
+        }
+    }
+
+    /// Mutable access to the node's modifiers
+    pub fn modifiers_mut(&mut self) -> &mut Modifiers {
+        match self {
+            Schema::String(s) => &mut s.modifiers,
+            Schema::Number(s) => &mut s.modifiers,
+            Schema::Decimal(s) => &mut s.modifiers,
+            Schema::BigInt(s) => &mut s.modifiers,
+            Schema::Date(s) => &mut s.modifiers,
+            Schema::DateTime(s) => &mut s.modifiers,
+            Schema::Boolean(s) => &mut s.modifiers,
+            Schema::Object(s) => &mut s.modifiers,
+            Schema::Array(s) => &mut s.modifiers,
+            Schema::Record(s) => &mut s.modifiers,
+            Schema::Tuple(s) => &mut s.modifiers,
+            Schema::Enum(s) => &mut s.modifiers,
+            Schema::Literal(s) => &mut s.modifiers,
+            Schema::Union(s) => &mut s.modifiers,
+            Schema::DiscriminatedUnion(s) => &mut s.modifiers,
+            Schema::Intersection(s) => &mut s.modifiers,
+            Schema::Ref(s) => &mut s.modifiers,
+        }
+    }
+
+    /// UI configuration of the node
+    pub fn ui(&self) -> Option<&UiConfig> {
+        match self {
+            Schema::String(s) => s.ui.as_ref(),
+            Schema::Number(s) => s.ui.as_ref(),
+            Schema::Decimal(s) => s.ui.as_ref(),
+            Schema::BigInt(s) => s.ui.as_ref(),
+            Schema::Date(s) => s.ui.as_ref(),
+            Schema::DateTime(s) => s.ui.as_ref(),
+            Schema::Boolean(s) => s.ui.as_ref(),
+            Schema::Object(s) => s.ui.as_ref(),
+            Schema::Array(s) => s.ui.as_ref(),
+            Schema::Record(s) => s.ui.as_ref(),
+            Schema::Tuple(s) => s.ui.as_ref(),
+            Schema::Enum(s) => s.ui.as_ref(),
+            Schema::Literal(s) => s.ui.as_ref(),
+            Schema::Union(s) => s.ui.as_ref(),
+            Schema::DiscriminatedUnion(s) => s.ui.as_ref(),
+            Schema::Intersection(s) => s.ui.as_ref(),
+            Schema::Ref(s) => s.ui.as_ref(),
+        }
+    }
+
+    /// Mutable access to the node's UI configuration
+    pub fn ui_mut(&mut self) -> &mut Option<UiConfig> {
+        match self {
+            Schema::String(s) => &mut s.ui,
+            Schema::Number(s) => &mut s.ui,
+            Schema::Decimal(s) => &mut s.ui,
+            Schema::BigInt(s) => &mut s.ui,
+            Schema::Date(s) => &mut s.ui,
+            Schema::DateTime(s) => &mut s.ui,
+            Schema::Boolean(s) => &mut s.ui,
+            Schema::Object(s) => &mut s.ui,
+            Schema::Array(s) => &mut s.ui,
+            Schema::Record(s) => &mut s.ui,
+            Schema::Tuple(s) => &mut s.ui,
+            Schema::Enum(s) => &mut s.ui,
+            Schema::Literal(s) => &mut s.ui,
+            Schema::Union(s) => &mut s.ui,
+            Schema::DiscriminatedUnion(s) => &mut s.ui,
+            Schema::Intersection(s) => &mut s.ui,
+            Schema::Ref(s) => &mut s.ui,
+        }
+    }
+
+    /// Human-readable field label from the UI configuration
+    pub fn label(&self) -> Option<&str> {
+        self.ui().and_then(|ui| ui.label.as_deref())
+    }
+
+    /// Type name as written in the schema JSON `type` tag
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Schema::String(_) => "string",
+            Schema::Number(_) => "number",
+            Schema::Decimal(_) => "decimal",
+            Schema::BigInt(_) => "bigint",
+            Schema::Date(_) => "date",
+            Schema::DateTime(_) => "datetime",
+            Schema::Boolean(_) => "boolean",
+            Schema::Object(_) => "object",
+            Schema::Array(_) => "array",
+            Schema::Record(_) => "record",
+            Schema::Tuple(_) => "tuple",
+            Schema::Enum(_) => "enum",
+            Schema::Literal(_) => "literal",
+            Schema::Union(_) => "union",
+            Schema::DiscriminatedUnion(_) => "discriminated_union",
+            Schema::Intersection(_) => "intersection",
+            Schema::Ref(_) => "ref",
+        }
+    }
+
+    /// Schemas nested directly in this node, including its definitions
+    pub(crate) fn children(&self) -> impl Iterator<Item = &Schema> {
+        let nested: Vec<&Schema> = match self {
+            Schema::Object(s) => s.shape.values().chain(s.catchall()).collect(),
+            Schema::Array(s) => vec![&s.items],
+            Schema::Record(s) => s.keys.as_deref().into_iter().chain([&*s.values]).collect(),
+            Schema::Tuple(s) => s.items.iter().chain(s.rest.as_deref()).collect(),
+            Schema::Union(s) => s.options.iter().collect(),
+            Schema::DiscriminatedUnion(s) => s.options.iter().collect(),
+            Schema::Intersection(s) => s.options.iter().collect(),
+            _ => Vec::new(),
+        };
+        nested.into_iter().chain(self.modifiers().definitions.values())
+    }
+
+    /// Follow references to the schema they name
+    ///
+    /// `None` if a name is not defined or the references only lead to each other.
+    pub fn resolve<'a>(&'a self, definitions: &'a IndexMap<String, Schema>) -> Option<&'a Schema> {
+        let mut current = self;
+        // A chain longer than the table revisits a name, so it never ends
+        for _ in 0..=definitions.len() {
+            match current {
+                Schema::Ref(reference) => current = definitions.get(&reference.name)?,
+                resolved => return Some(resolved),
+            }
+        }
+        None
+    }
+}
+
+/// Presence and input modifiers that apply to any schema node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Modifiers {
+    /// A missing object key passes validation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+    /// `null` passes validation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    /// Value substituted for a missing object key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Convert string input to the node's type, overriding the global option
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coerce: Option<bool>,
+    /// Named schemas that `ref` nodes resolve to
+    ///
+    /// Only allowed on the root schema; reading JSON with definitions on a
+    /// nested node fails.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub definitions: IndexMap<String, Schema>,
+}
+
+impl Modifiers {
+    pub fn is_optional(&self) -> bool {
+        self.optional == Some(true)
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable == Some(true)
+    }
+}
+
+/// String schema options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StringSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    /// What `min` and `max` count, Unicode scalar values when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length_unit: Option<LengthUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<bool>,
+    /// Schemes `url` accepts, `http`, `https`, `ws`, `wss` and `ftp` when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schemes: Option<Vec<String>>,
+    /// Whether `email` and `url` domains need a top-level domain; by default
+    /// emails do and URLs (e.g. `http://localhost`) do not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_tld: Option<bool>,
+    /// Whether `email` and `url` accept internationalized (Unicode or
+    /// punycode) domains, allowed when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_idn: Option<bool>,
+    /// Whether `email` and `url` accept IP-literal hosts, allowed when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_ip: Option<bool>,
+    /// Longest `email` or `url` accepted, in bytes; 254 for emails (RFC 5321)
+    /// and unlimited for URLs when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ulid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cuid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv4: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cidr: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semver: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<bool>,
+    /// Offsets accepted by `datetime`, any RFC 3339 offset when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<DateTimeOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// JavaScript-style regex flags for `pattern`, e.g. `"i"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_with: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_with: Option<String>,
+    /// Substring the value must contain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes: Option<String>,
+    /// Substring the value must not contain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excludes: Option<String>,
+    /// Characters the value may consist of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<Charset>,
+    /// Strip leading and trailing whitespace before validating
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trim: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_lower_case: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_upper_case: Option<bool>,
+    /// Unicode normalization form applied before the other transforms
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalization>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<StringMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Timezone offsets accepted by a date-time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateTimeOffset {
+    /// `Z` or a numeric `±HH:MM` offset is required, as in RFC 3339
+    #[default]
+    Any,
+    /// Only `Z` is accepted
+    Utc,
+    /// No offset is allowed, as produced by `<input type="datetime-local">`
+    Local,
+    /// An offset may be given or left out
+    Optional,
+}
+
+/// Set of allowed characters: the union of some classes and extra characters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Charset {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classes: Vec<CharClass>,
+    /// Individual characters allowed in addition to the classes, e.g. `"-_"`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub chars: String,
+}
+
+impl Charset {
+    pub fn contains(&self, c: char) -> bool {
+        self.classes.iter().any(|class| class.contains(c)) || self.chars.contains(c)
+    }
+}
+
+/// Named character class for `Charset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CharClass {
+    /// ASCII letters
+    Alpha,
+    /// ASCII lowercase letters
+    Lower,
+    /// ASCII uppercase letters
+    Upper,
+    /// ASCII digits
+    Digit,
+    /// ASCII letters and digits
+    Alphanumeric,
+    /// Hexadecimal digits
+    Hex,
+    /// Letters of any script
+    Letter,
+    /// Unicode whitespace
+    Whitespace,
+    /// ASCII punctuation
+    Punctuation,
+    /// Any ASCII character
+    Ascii,
+}
+
+impl CharClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            CharClass::Alpha => c.is_ascii_alphabetic(),
+            CharClass::Lower => c.is_ascii_lowercase(),
+            CharClass::Upper => c.is_ascii_uppercase(),
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Alphanumeric => c.is_ascii_alphanumeric(),
+            CharClass::Hex => c.is_ascii_hexdigit(),
+            CharClass::Letter => c.is_alphabetic(),
+            CharClass::Whitespace => c.is_whitespace(),
+            CharClass::Punctuation => c.is_ascii_punctuation(),
+            CharClass::Ascii => c.is_ascii(),
+        }
+    }
+}
+
+/// Unit a string's length is measured in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    /// Unicode scalar values, so "José" has length 4
+    #[default]
+    CodePoints,
+    /// User-perceived characters, so an emoji with modifiers counts once
+    Graphemes,
+    /// UTF-16 code units, matching JavaScript `length` and `maxlength`
+    Utf16,
+}
+
+impl LengthUnit {
+    /// Name reported in the `unit` error param
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LengthUnit::CodePoints => "code_points",
+            LengthUnit::Graphemes => "graphemes",
+            LengthUnit::Utf16 => "utf16",
+        }
+    }
+}
+
+/// Unicode normalization form for string input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalization {
+    /// Canonical composition
+    Nfc,
+    /// Compatibility composition, folding e.g. full-width letters and ligatures
+    Nfkc,
+}
+
+/// Number schema options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NumberSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_max: Option<f64>,
+    /// Step the value must be a whole multiple of, e.g. `0.01` for cents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integer: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_negative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_positive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finite: Option<bool>,
+    /// Require an integer between -(2^53 - 1) and 2^53 - 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_integer: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<NumberMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Decimal schema options
+///
+/// Accepts JSON numbers and numeric strings and checks them on their exact
+/// digits, e.g. money amounts that must not pick up float rounding.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecimalSchema {
+    /// Maximum total number of digits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+    /// Maximum number of digits after the decimal point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<Numeric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<Numeric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<DecimalMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Big integer schema options
+///
+/// Accepts integral JSON numbers and numeric strings of any size, e.g. 64-bit IDs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BigIntSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<Numeric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<Numeric>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<BigIntMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Calendar date schema options
+///
+/// Accepts `YYYY-MM-DD` strings. Relative bounds and ages are measured from
+/// `ValidationOptions::now`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DateSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<DateBound>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<DateBound>,
+    /// Minimum whole years between the date and today, e.g. `18` for adults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u32>,
+    /// Also accept milliseconds since the Unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_millis: Option<bool>,
+    /// Offset that decides which date "today" and epoch timestamps fall on (default UTC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utc_offset: Option<UtcOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<DateMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Date-time schema options
+///
+/// Accepts RFC 3339 strings and compares them as instants.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DateTimeSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<DateBound>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<DateBound>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u32>,
+    /// Also accept milliseconds since the Unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_millis: Option<bool>,
+    /// Whether the input must, may or must not carry a UTC offset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<DateTimeOffset>,
+    /// Offset assumed for input without one (default UTC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utc_offset: Option<UtcOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<DateMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Boolean schema options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BooleanSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<BooleanMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Object schema options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObjectSchema {
+    /// Fields in declaration order, which is also the order errors are reported in
+    pub shape: IndexMap<String, Schema>,
+    /// How keys missing from the shape are handled, `strip` when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unknown_keys: Option<UnknownKeys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<ObjectMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+impl ObjectSchema {
+    /// Schema that extra keys are validated against, if any
+    pub fn catchall(&self) -> Option<&Schema> {
+        match &self.unknown_keys {
+            Some(UnknownKeys::Catchall(schema)) => Some(schema),
+            _ => None,
+        }
+    }
+}
+
+/// Policy for object keys that are not declared in the shape
+///
+/// Serialized as `"strip"`, `"strict"`, `"passthrough"` or `{"catchall": <schema>}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownKeys {
+    /// Accept extra keys and leave them out of the parse output
+    #[default]
+    Strip,
+    /// Report extra keys as `unrecognized_keys`
+    Strict,
+    /// Accept extra keys and keep them as-is
+    Passthrough,
+    /// Validate extra values against a schema and keep their output
+    Catchall(Box<Schema>),
+}
+
+/// Array schema options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArraySchema {
+    pub items: Box<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<ArrayMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+impl ArraySchema {
+    pub fn new(items: Schema) -> Self {
+        Self {
+            items: Box::new(items),
+            min: None,
+            max: None,
+            unique: None,
+            ui: None,
+            messages: None,
+            modifiers: Modifiers::default(),
+        }
+    }
+}
+
+/// Record schema options: an object with dynamic keys and uniform values
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordSchema {
+    /// Schema each key is validated against as a string, e.g. a pattern or enum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<Box<Schema>>,
+    pub values: Box<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_entries: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<RecordMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+impl RecordSchema {
+    pub fn new(values: Schema) -> Self {
+        Self {
+            keys: None,
+            values: Box::new(values),
+            min_entries: None,
+            max_entries: None,
+            ui: None,
+            messages: None,
+            modifiers: Modifiers::default(),
+        }
+    }
+}
+
+/// Tuple schema options: a fixed-length array typed by position
+///
+/// Trailing positions that are optional or have a default may be left out.
+/// Elements past `items` are validated against `rest`, or rejected without it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TupleSchema {
+    pub items: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rest: Option<Box<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<TupleMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Enum schema options: the value must equal one of `values`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnumSchema {
+    /// Allowed string or number values
+    pub values: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<EnumMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Literal schema options: the value must equal `value` exactly
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiteralSchema {
+    pub value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<LiteralMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Union schema options: the value must match at least one option
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnionSchema {
+    pub options: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<UnionMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Discriminated union schema options
+///
+/// Each option is an object schema whose `discriminator` field is a literal
+/// (or enum); the value's tag selects the single option it is validated against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscriminatedUnionSchema {
+    pub discriminator: String,
+    pub options: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<DiscriminatedUnionMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Intersection schema options: the value must match every option
+///
+/// The outputs of the options are merged, so an intersection of object
+/// schemas keeps the fields of all of them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntersectionSchema {
+    pub options: Vec<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<IntersectionMessages>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+/// Reference to a schema in the root `definitions` table
+///
+/// References make recursive shapes such as comment threads or category
+/// trees expressible. Modifiers on the reference apply at the point of use.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RefSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfig>,
+    #[serde(flatten)]
+    pub modifiers: Modifiers,
+}
+
+impl RefSchema {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Custom error messages for string validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StringMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invalid_type: Option<String>,
@@ -64,11 +831,51 @@ pub struct StringMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ulid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv4: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cidr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_with: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_with: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excludes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
 }
 
 /// Custom error messages for number validation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NumberMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invalid_type: Option<String>,
@@ -80,26 +887,169 @@ pub struct NumberMessages {
     pub integer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub positive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_max: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_negative: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_positive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_integer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coercion_failed: Option<String>,
+}
+
+/// Custom error messages for decimal validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecimalMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+}
+
+/// Custom error messages for big integer validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BigIntMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+}
+
+/// Custom error messages for date and date-time validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DateMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_age: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<String>,
 }
 
 /// Custom error messages for boolean validation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BooleanMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coercion_failed: Option<String>,
 }
 
 /// Custom error messages for object validation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ObjectMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invalid_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unrecognized_keys: Option<String>,
+}
+
+/// Custom error messages for array validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArrayMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<String>,
+}
+
+/// Custom error messages for record validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_entries: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<String>,
+}
+
+/// Custom error messages for tuple validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TupleMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_small: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_big: Option<String>,
+}
+
+/// Custom error messages for enum validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnumMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_enum_value: Option<String>,
+}
+
+/// Custom error messages for literal validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiteralMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_literal: Option<String>,
+}
+
+/// Custom error messages for intersection validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntersectionMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_intersection: Option<String>,
+}
+
+/// Custom error messages for union validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnionMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_union: Option<String>,
+}
+
+/// Custom error messages for discriminated union validation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscriminatedUnionMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_union_discriminator: Option<String>,
 }
 
 /// UI configuration for forms
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UiConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
@@ -115,6 +1065,10 @@ pub struct ValidationError {
     pub path: Vec<String>,
     pub code: String,
     pub message: String,
+    /// Values behind the message (limits, actual values, expected types) so
+    /// clients can render their own text
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, Value>,
 }
 
 impl ValidationError {
@@ -123,6 +1077,129 @@ impl ValidationError {
             path,
             code: code.into(),
             message: message.into(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a structured parameter
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Fill `{placeholder}`s in the message
+    ///
+    /// `{label}`, `{path}` and `{value}` describe the field; `{min}`, `{max}`
+    /// and `{length}` are shorthands for the `minimum`, `maximum` and `actual`
+    /// params, and any other param can be referenced by name. Unknown
+    /// placeholders are left untouched.
+    pub fn interpolate(mut self, label: Option<&str>, value: Option<&Value>) -> Self {
+        if !self.message.contains('{') {
+            return self;
+        }
+
+        let mut rendered = String::with_capacity(self.message.len());
+        let mut rest = self.message.as_str();
+
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+
+            match self.placeholder(&after[..end], label, value) {
+                Some(text) => rendered.push_str(&text),
+                None => rendered.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &after[end + 1..];
+        }
+        rendered.push_str(rest);
+
+        self.message = rendered;
+        self
+    }
+
+    fn placeholder(&self, name: &str, label: Option<&str>, value: Option<&Value>) -> Option<String> {
+        match name {
+            "label" => Some(
+                label
+                    .or_else(|| self.path.last().map(String::as_str))
+                    .unwrap_or("value")
+                    .to_string(),
+            ),
+            "path" => Some(self.path.join(".")),
+            "value" => Some(value.map(render_param).unwrap_or_default()),
+            "min" => self.params.get("minimum").map(render_param),
+            "max" => self.params.get("maximum").map(render_param),
+            "length" => self.params.get("actual").map(render_param),
+            other => self.params.get(other).map(render_param),
+        }
+    }
+}
+
+/// Render a param for a message: strings as-is, whole numbers without a
+/// fraction, lists as `'a' | 'b'`
+fn render_param(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => f.to_string(),
+            _ => n.to_string(),
+        },
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(s) => format!("'{}'", s),
+                other => render_param(other),
+            })
+            .collect::<Vec<_>>()
+            .join(" | "),
+        other => other.to_string(),
+    }
+}
+
+/// Error in the schema definition itself rather than in the validated value
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SchemaError {
+    #[error("Invalid pattern '{pattern}' at '{}': {reason}", path.join("."))]
+    InvalidPattern {
+        path: Vec<String>,
+        pattern: String,
+        reason: String,
+    },
+    #[error("Unknown schema reference '{name}' at '{}'", path.join("."))]
+    UnknownReference { path: Vec<String>, name: String },
+    #[error("Schema reference '{name}' at '{}' only refers to other references", path.join("."))]
+    CircularReference { path: Vec<String>, name: String },
+    #[error("'{operation}' requires an object schema, found {found}")]
+    NotAnObject { operation: &'static str, found: &'static str },
+    #[error("Definition '{name}' differs between the combined schemas")]
+    ConflictingDefinition { name: String },
+}
+
+impl From<SchemaError> for ValidationError {
+    fn from(error: SchemaError) -> Self {
+        let message = error.to_string();
+        match error {
+            SchemaError::InvalidPattern { path, pattern, reason } => {
+                ValidationError::new(path, "invalid_schema", message)
+                    .with_param("pattern", pattern)
+                    .with_param("reason", reason)
+            }
+            SchemaError::UnknownReference { path, name }
+            | SchemaError::CircularReference { path, name } => {
+                ValidationError::new(path, "invalid_schema", message).with_param("reference", name)
+            }
+            SchemaError::NotAnObject { operation, found } => {
+                ValidationError::new(Vec::new(), "invalid_schema", message)
+                    .with_param("operation", operation)
+                    .with_param("found", found)
+            }
+            SchemaError::ConflictingDefinition { name } => {
+                ValidationError::new(Vec::new(), "invalid_schema", message).with_param("definition", name)
+            }
         }
     }
 }
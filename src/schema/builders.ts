@@ -1,32 +1,128 @@
 import type {
   StringSchema,
   NumberSchema,
+  DecimalSchema,
+  BigIntSchema,
+  DateSchema,
+  DateTimeSchema,
   BooleanSchema,
   ObjectSchema,
+  ArraySchema,
+  RecordSchema,
+  TupleSchema,
+  EnumSchema,
+  LiteralSchema,
+  UnionSchema,
+  DiscriminatedUnionSchema,
+  IntersectionSchema,
+  ComposeSchema,
+  CompositionStep,
+  RefSchema,
+  Schema,
+  Modifiers,
   UiConfig,
   StringMessages,
   NumberMessages,
+  DecimalMessages,
+  BigIntMessages,
+  DateMessages,
   BooleanMessages,
   ObjectMessages,
+  ArrayMessages,
+  RecordMessages,
+  TupleMessages,
+  EnumMessages,
+  LiteralMessages,
+  UnionMessages,
+  DiscriminatedUnionMessages,
+  IntersectionMessages,
+  Normalization,
+  LengthUnit,
+  DateTimeOffset,
+  CharClass,
+  DateBound,
 } from "./types";
 
 /**
  * Base class for all schema builders
  */
 export abstract class SchemaBuilder<T> {
+  protected abstract schema: Modifiers;
+
   abstract toJSON(): any;
 
   /**
    * Add UI configuration for forms
    */
   abstract ui(config: UiConfig): this;
+
+  /**
+   * Allow the field to be missing from its parent object
+   * Call it after the type-specific rules, which the widened builder no longer offers
+   */
+  optional(): SchemaBuilder<T | undefined> {
+    this.schema.optional = true;
+    return this as unknown as SchemaBuilder<T | undefined>;
+  }
+
+  /**
+   * Allow null values
+   * Call it after the type-specific rules, which the widened builder no longer offers
+   */
+  nullable(): SchemaBuilder<T | null> {
+    this.schema.nullable = true;
+    return this as unknown as SchemaBuilder<T | null>;
+  }
+
+  /**
+   * Value used when the field is missing from its parent object
+   * The parsed output always has the field, so it is never undefined
+   */
+  default(value: Exclude<T, undefined>): SchemaBuilder<Exclude<T, undefined>> {
+    this.schema.default = value;
+    return this as unknown as SchemaBuilder<Exclude<T, undefined>>;
+  }
+
+  /**
+   * Convert string input (e.g. from form fields) to this node's type
+   * An empty string counts as a missing value
+   */
+  coerce(enabled = true): this {
+    this.schema.coerce = enabled;
+    return this;
+  }
+
+  /**
+   * Declare named schemas that r.ref() resolves to
+   * Only allowed on the root schema; nested definitions are rejected
+   */
+  definitions(definitions: Record<string, SchemaBuilder<any>>): this {
+    this.schema.definitions = Object.fromEntries(
+      Object.entries(definitions).map(([name, schema]) => [name, schema.toJSON()]),
+    );
+    return this;
+  }
+}
+
+/**
+ * Rules shared by email() and url()
+ */
+export interface HostOptions {
+  /** Require a top-level domain (default: true for email, false for URLs) */
+  requireTld?: boolean;
+  /** Accept internationalized domains (default: true) */
+  allowIdn?: boolean;
+  /** Accept IP-literal hosts (default: true) */
+  allowIp?: boolean;
+  /** Longest value accepted, in bytes (default: 254 for email, unlimited for URLs) */
+  maxLength?: number;
 }
 
 /**
  * String schema builder with fluent API
  */
 export class ZString extends SchemaBuilder<string> {
-  private schema: StringSchema;
+  protected schema: StringSchema;
 
   constructor() {
     super();
@@ -52,24 +148,243 @@ export class ZString extends SchemaBuilder<string> {
   /**
    * Mark as email validation
    */
-  email(): this {
+  email(options: HostOptions = {}): this {
     this.schema.email = true;
+    this.hostOptions(options);
     return this;
   }
 
   /**
    * Mark as URL validation
+   * Accepts http, https, ws, wss and ftp URLs unless schemes are given
    */
-  url(): this {
+  url(options: HostOptions & { schemes?: string[] } = {}): this {
     this.schema.url = true;
+    this.schema.schemes = options.schemes;
+    this.hostOptions(options);
+    return this;
+  }
+
+  private hostOptions(options: HostOptions): void {
+    this.schema.require_tld = options.requireTld;
+    this.schema.allow_idn = options.allowIdn;
+    this.schema.allow_ip = options.allowIp;
+    this.schema.max_length = options.maxLength;
+  }
+
+  /**
+   * Mark as UUID validation
+   */
+  uuid(): this {
+    this.schema.uuid = true;
+    return this;
+  }
+
+  /**
+   * Mark as ULID validation
+   */
+  ulid(): this {
+    this.schema.ulid = true;
+    return this;
+  }
+
+  /**
+   * Mark as CUID validation
+   */
+  cuid(): this {
+    this.schema.cuid = true;
+    return this;
+  }
+
+  /**
+   * Mark as IPv4 address validation
+   */
+  ipv4(): this {
+    this.schema.ipv4 = true;
+    return this;
+  }
+
+  /**
+   * Mark as IPv6 address validation
+   */
+  ipv6(): this {
+    this.schema.ipv6 = true;
+    return this;
+  }
+
+  /**
+   * Mark as CIDR range validation (IPv4 or IPv6)
+   */
+  cidr(): this {
+    this.schema.cidr = true;
+    return this;
+  }
+
+  /**
+   * Mark as hostname validation
+   */
+  hostname(): this {
+    this.schema.hostname = true;
+    return this;
+  }
+
+  /**
+   * Mark as date validation (YYYY-MM-DD)
+   */
+  date(): this {
+    this.schema.date = true;
+    return this;
+  }
+
+  /**
+   * Mark as time of day validation (HH:MM[:SS[.fraction]])
+   */
+  time(): this {
+    this.schema.time = true;
+    return this;
+  }
+
+  /**
+   * Mark as RFC 3339 date-time validation
+   */
+  datetime(options: { offset?: DateTimeOffset } = {}): this {
+    this.schema.datetime = true;
+    this.schema.offset = options.offset;
+    return this;
+  }
+
+  /**
+   * Mark as ISO 8601 duration validation
+   */
+  duration(): this {
+    this.schema.duration = true;
+    return this;
+  }
+
+  /**
+   * Mark as base64 validation
+   */
+  base64(): this {
+    this.schema.base64 = true;
+    return this;
+  }
+
+  /**
+   * Mark as hexadecimal validation
+   */
+  hex(): this {
+    this.schema.hex = true;
+    return this;
+  }
+
+  /**
+   * Mark as semantic version validation
+   */
+  semver(): this {
+    this.schema.semver = true;
+    return this;
+  }
+
+  /**
+   * Mark as JSON Web Token shape validation
+   */
+  jwt(): this {
+    this.schema.jwt = true;
     return this;
   }
 
   /**
    * Add pattern matching
+   * Accepts a regex source with optional flags, or a RegExp
+   */
+  pattern(regex: string | RegExp, flags?: string): this {
+    if (regex instanceof RegExp) {
+      this.schema.pattern = regex.source;
+      this.schema.flags = flags ?? (regex.flags || undefined);
+    } else {
+      this.schema.pattern = regex;
+      this.schema.flags = flags;
+    }
+    return this;
+  }
+
+  /**
+   * Require the value to start with a prefix
+   */
+  startsWith(prefix: string): this {
+    this.schema.starts_with = prefix;
+    return this;
+  }
+
+  /**
+   * Require the value to end with a suffix
+   */
+  endsWith(suffix: string): this {
+    this.schema.ends_with = suffix;
+    return this;
+  }
+
+  /**
+   * Require the value to contain a substring
+   */
+  includes(substring: string): this {
+    this.schema.includes = substring;
+    return this;
+  }
+
+  /**
+   * Reject values containing a substring
+   */
+  excludes(substring: string): this {
+    this.schema.excludes = substring;
+    return this;
+  }
+
+  /**
+   * Restrict the value to characters from the given classes plus `chars`
+   */
+  charset(classes: CharClass[], chars?: string): this {
+    this.schema.charset = { classes, chars };
+    return this;
+  }
+
+  /**
+   * Set the unit min and max count in (default: Unicode code points)
+   */
+  lengthUnit(unit: LengthUnit): this {
+    this.schema.length_unit = unit;
+    return this;
+  }
+
+  /**
+   * Strip leading and trailing whitespace before validating
+   */
+  trim(): this {
+    this.schema.trim = true;
+    return this;
+  }
+
+  /**
+   * Lowercase the value before validating
+   */
+  toLowerCase(): this {
+    this.schema.to_lower_case = true;
+    return this;
+  }
+
+  /**
+   * Uppercase the value before validating
+   */
+  toUpperCase(): this {
+    this.schema.to_upper_case = true;
+    return this;
+  }
+
+  /**
+   * Apply Unicode normalization before the other transforms
    */
-  pattern(regex: string): this {
-    this.schema.pattern = regex;
+  normalize(form: Normalization = "nfc"): this {
+    this.schema.normalize = form;
     return this;
   }
 
@@ -101,7 +416,7 @@ export class ZString extends SchemaBuilder<string> {
  * Number schema builder with fluent API
  */
 export class ZNumber extends SchemaBuilder<number> {
-  private schema: NumberSchema;
+  protected schema: NumberSchema;
 
   constructor() {
     super();
@@ -124,6 +439,29 @@ export class ZNumber extends SchemaBuilder<number> {
     return this;
   }
 
+  /**
+   * Require values greater than `value`
+   */
+  gt(value: number): this {
+    this.schema.exclusive_min = value;
+    return this;
+  }
+
+  /**
+   * Require values less than `value`
+   */
+  lt(value: number): this {
+    this.schema.exclusive_max = value;
+    return this;
+  }
+
+  /**
+   * Require a whole multiple of `step`; decimal steps such as 0.01 tolerate float rounding
+   */
+  multipleOf(step: number): this {
+    this.schema.multiple_of = step;
+    return this;
+  }
   /**
    * Require integer values only
    */
@@ -140,6 +478,46 @@ export class ZNumber extends SchemaBuilder<number> {
     return this;
   }
 
+  /**
+   * Require negative values only
+   */
+  negative(): this {
+    this.schema.negative = true;
+    return this;
+  }
+
+  /**
+   * Require zero or positive values
+   */
+  nonNegative(): this {
+    this.schema.non_negative = true;
+    return this;
+  }
+
+  /**
+   * Require zero or negative values
+   */
+  nonPositive(): this {
+    this.schema.non_positive = true;
+    return this;
+  }
+
+  /**
+   * Reject infinite values
+   */
+  finite(): this {
+    this.schema.finite = true;
+    return this;
+  }
+
+  /**
+   * Require an integer within ±(2^53 - 1)
+   */
+  safe(): this {
+    this.schema.safe_integer = true;
+    return this;
+  }
+
   /**
    * Add UI configuration
    */
@@ -165,14 +543,47 @@ export class ZNumber extends SchemaBuilder<number> {
 }
 
 /**
- * Boolean schema builder with fluent API
+ * Exact decimal schema builder with fluent API
+ * Values stay as given: numbers as numbers, numeric strings as strings
  */
-export class ZBoolean extends SchemaBuilder<boolean> {
-  private schema: BooleanSchema;
+export class ZDecimal extends SchemaBuilder<string | number> {
+  protected schema: DecimalSchema;
 
   constructor() {
     super();
-    this.schema = { type: "boolean" };
+    this.schema = { type: "decimal" };
+  }
+
+  /**
+   * Set the maximum total number of digits
+   */
+  precision(digits: number): this {
+    this.schema.precision = digits;
+    return this;
+  }
+
+  /**
+   * Set the maximum number of digits after the decimal point
+   */
+  scale(digits: number): this {
+    this.schema.scale = digits;
+    return this;
+  }
+
+  /**
+   * Set minimum value constraint; pass a string to keep every digit
+   */
+  min(value: string | number): this {
+    this.schema.min = value;
+    return this;
+  }
+
+  /**
+   * Set maximum value constraint; pass a string to keep every digit
+   */
+  max(value: string | number): this {
+    this.schema.max = value;
+    return this;
   }
 
   /**
@@ -186,7 +597,7 @@ export class ZBoolean extends SchemaBuilder<boolean> {
   /**
    * Add custom error messages
    */
-  messages(messages: BooleanMessages): this {
+  messages(messages: DecimalMessages): this {
     this.schema.messages = messages;
     return this;
   }
@@ -194,34 +605,37 @@ export class ZBoolean extends SchemaBuilder<boolean> {
   /**
    * Serialize to JSON for Rust validation
    */
-  toJSON(): BooleanSchema {
+  toJSON(): DecimalSchema {
     return { ...this.schema };
   }
 }
 
 /**
- * Object schema builder with fluent API
+ * Big integer schema builder with fluent API
+ * Values stay as given: numbers as numbers, numeric strings as strings
  */
-export class ZObject<
-  T extends Record<string, SchemaBuilder<any>>,
-> extends SchemaBuilder<{
-  [K in keyof T]: T[K] extends SchemaBuilder<infer U> ? U : never;
-}> {
-  private schema: ObjectSchema;
+export class ZBigInt extends SchemaBuilder<string | number> {
+  protected schema: BigIntSchema;
 
-  constructor(shape: T) {
+  constructor() {
     super();
+    this.schema = { type: "bigint" };
+  }
 
-    // Convert SchemaBuilder instances to their JSON representation
-    const jsonShape: Record<string, any> = {};
-    for (const [key, value] of Object.entries(shape)) {
-      jsonShape[key] = value.toJSON();
-    }
+  /**
+   * Set minimum value constraint
+   */
+  min(value: string | number | bigint): this {
+    this.schema.min = typeof value === "bigint" ? value.toString() : value;
+    return this;
+  }
 
-    this.schema = {
-      type: "object",
-      shape: jsonShape,
-    };
+  /**
+   * Set maximum value constraint
+   */
+  max(value: string | number | bigint): this {
+    this.schema.max = typeof value === "bigint" ? value.toString() : value;
+    return this;
   }
 
   /**
@@ -235,7 +649,7 @@ export class ZObject<
   /**
    * Add custom error messages
    */
-  messages(messages: ObjectMessages): this {
+  messages(messages: BigIntMessages): this {
     this.schema.messages = messages;
     return this;
   }
@@ -243,7 +657,838 @@ export class ZObject<
   /**
    * Serialize to JSON for Rust validation
    */
-  toJSON(): ObjectSchema {
+  toJSON(): BigIntSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Calendar date schema builder with fluent API
+ */
+export class ZDate extends SchemaBuilder<string> {
+  protected schema: DateSchema;
+
+  constructor() {
+    super();
+    this.schema = { type: "date" };
+  }
+
+  /**
+   * Set the earliest allowed value, e.g. "now+P1D" or a Date
+   */
+  min(bound: DateBound | Date): this {
+    this.schema.min = bound instanceof Date ? bound.toISOString().slice(0, 10) : bound;
+    return this;
+  }
+
+  /**
+   * Set the latest allowed value, e.g. "now" or a Date
+   */
+  max(bound: DateBound | Date): this {
+    this.schema.max = bound instanceof Date ? bound.toISOString().slice(0, 10) : bound;
+    return this;
+  }
+
+  /**
+   * Require at least this many whole years before now
+   */
+  minAge(years: number): this {
+    this.schema.min_age = years;
+    return this;
+  }
+
+  /**
+   * Allow at most this many whole years before now
+   */
+  maxAge(years: number): this {
+    this.schema.max_age = years;
+    return this;
+  }
+
+  /**
+   * Also accept milliseconds since the Unix epoch
+   */
+  epochMillis(): this {
+    this.schema.epoch_millis = true;
+    return this;
+  }
+
+  /**
+   * Set the offset that decides which date "now" and epoch input fall on
+   */
+  utcOffset(offset: string): this {
+    this.schema.utc_offset = offset;
+    return this;
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: DateMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): DateSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Date-time schema builder with fluent API
+ */
+export class ZDateTime extends SchemaBuilder<string> {
+  protected schema: DateTimeSchema;
+
+  constructor() {
+    super();
+    this.schema = { type: "datetime" };
+  }
+
+  /**
+   * Set the earliest allowed value, e.g. "now+P1D" or a Date
+   */
+  min(bound: DateBound | Date): this {
+    this.schema.min = bound instanceof Date ? bound.toISOString() : bound;
+    return this;
+  }
+
+  /**
+   * Set the latest allowed value, e.g. "now" or a Date
+   */
+  max(bound: DateBound | Date): this {
+    this.schema.max = bound instanceof Date ? bound.toISOString() : bound;
+    return this;
+  }
+
+  /**
+   * Require at least this many whole years before now
+   */
+  minAge(years: number): this {
+    this.schema.min_age = years;
+    return this;
+  }
+
+  /**
+   * Allow at most this many whole years before now
+   */
+  maxAge(years: number): this {
+    this.schema.max_age = years;
+    return this;
+  }
+
+  /**
+   * Also accept milliseconds since the Unix epoch
+   */
+  epochMillis(): this {
+    this.schema.epoch_millis = true;
+    return this;
+  }
+
+  /**
+   * Set whether input must, may or must not carry a UTC offset
+   */
+  offset(policy: DateTimeOffset): this {
+    this.schema.offset = policy;
+    return this;
+  }
+
+  /**
+   * Set the offset assumed for input without one (default UTC)
+   */
+  utcOffset(offset: string): this {
+    this.schema.utc_offset = offset;
+    return this;
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: DateMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): DateTimeSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Boolean schema builder with fluent API
+ */
+export class ZBoolean extends SchemaBuilder<boolean> {
+  protected schema: BooleanSchema;
+
+  constructor() {
+    super();
+    this.schema = { type: "boolean" };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: BooleanMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): BooleanSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Output type of an object shape
+ */
+type ShapeOutput<T extends Record<string, SchemaBuilder<any>>> = {
+  [K in keyof T as undefined extends FieldOutput<T[K]> ? never : K]: FieldOutput<T[K]>;
+} & {
+  [K in keyof T as undefined extends FieldOutput<T[K]> ? K : never]?: FieldOutput<T[K]>;
+};
+
+/**
+ * Output type of one object field
+ */
+type FieldOutput<B> = B extends SchemaBuilder<infer U> ? U : never;
+
+/**
+ * Output type with fields optional at every level
+ */
+type DeepPartial<T> = T extends (infer U)[]
+  ? DeepPartial<U>[]
+  : T extends object
+    ? { [K in keyof T]?: DeepPartial<T[K]> }
+    : T;
+
+/**
+ * Combinators shared by object schemas and schemas derived from them
+ * Each returns a new schema; the combinators themselves run in Rust when
+ * the validator reads the schema
+ */
+export abstract class ObjectBuilder<T extends object> extends SchemaBuilder<T> {
+  /**
+   * Add fields, replacing fields of the same name
+   */
+  extend<E extends Record<string, SchemaBuilder<any>>>(
+    shape: E,
+  ): ZComposed<Omit<T, keyof E> & ShapeOutput<E>> {
+    const jsonShape: Record<string, Schema> = {};
+    for (const [key, value] of Object.entries(shape)) {
+      jsonShape[key] = value.toJSON();
+    }
+    return this.compose({ op: "extend", shape: jsonShape });
+  }
+
+  /**
+   * Combine with another object schema
+   * Its fields and unknown-key policy take precedence
+   */
+  merge<U extends object>(other: ObjectBuilder<U>): ZComposed<Omit<T, keyof U> & U> {
+    return this.compose({ op: "merge", schema: other.toJSON() });
+  }
+
+  /**
+   * Keep only the given fields
+   */
+  pick<K extends keyof T & string>(keys: K[]): ZComposed<Pick<T, K>> {
+    return this.compose({ op: "pick", keys });
+  }
+
+  /**
+   * Drop the given fields
+   */
+  omit<K extends keyof T & string>(keys: K[]): ZComposed<Omit<T, K>> {
+    return this.compose({ op: "omit", keys });
+  }
+
+  /**
+   * Make every field optional
+   */
+  partial(): ZComposed<Partial<T>> {
+    return this.compose({ op: "partial" });
+  }
+
+  /**
+   * Make fields optional at every level of nesting
+   */
+  deepPartial(): ZComposed<DeepPartial<T>> {
+    return this.compose({ op: "deep_partial" });
+  }
+
+  /**
+   * Make every field required; defaults still fill missing keys
+   */
+  required(): ZComposed<Required<T>> {
+    return this.compose({ op: "required" });
+  }
+
+  private compose<R extends object>(step: CompositionStep): ZComposed<R> {
+    return new ZComposed<R>(this.toJSON(), [step]);
+  }
+}
+
+/**
+ * Object schema builder with fluent API
+ */
+export class ZObject<
+  T extends Record<string, SchemaBuilder<any>>,
+> extends ObjectBuilder<ShapeOutput<T>> {
+  protected schema: ObjectSchema;
+
+  constructor(shape: T) {
+    super();
+
+    // Convert SchemaBuilder instances to their JSON representation
+    const jsonShape: Record<string, any> = {};
+    for (const [key, value] of Object.entries(shape)) {
+      jsonShape[key] = value.toJSON();
+    }
+
+    this.schema = {
+      type: "object",
+      shape: jsonShape,
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Leave unknown keys out of the parsed output (default)
+   */
+  strip(): this {
+    this.schema.unknown_keys = "strip";
+    return this;
+  }
+
+  /**
+   * Report unknown keys as an `unrecognized_keys` error
+   */
+  strict(): this {
+    this.schema.unknown_keys = "strict";
+    return this;
+  }
+
+  /**
+   * Keep unknown keys in the parsed output as-is
+   */
+  passthrough(): this {
+    this.schema.unknown_keys = "passthrough";
+    return this;
+  }
+
+  /**
+   * Validate unknown keys against a schema
+   */
+  catchall(schema: SchemaBuilder<any>): this {
+    this.schema.unknown_keys = { catchall: schema.toJSON() };
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: ObjectMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): ObjectSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Object schema derived by extend, merge, pick, omit, partial,
+ * deepPartial or required
+ */
+export class ZComposed<T extends object> extends ObjectBuilder<T> {
+  protected schema: ComposeSchema;
+
+  constructor(schema: Schema, steps: CompositionStep[]) {
+    super();
+    this.schema = { type: "compose", schema, steps };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): ComposeSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Array schema builder with fluent API
+ */
+export class ZArray<T extends SchemaBuilder<any>> extends SchemaBuilder<
+  T extends SchemaBuilder<infer U> ? U[] : never
+> {
+  protected schema: ArraySchema;
+
+  constructor(items: T) {
+    super();
+    this.schema = {
+      type: "array",
+      items: items.toJSON(),
+    };
+  }
+
+  /**
+   * Set minimum number of items
+   */
+  min(length: number): this {
+    this.schema.min = length;
+    return this;
+  }
+
+  /**
+   * Set maximum number of items
+   */
+  max(length: number): this {
+    this.schema.max = length;
+    return this;
+  }
+
+  /**
+   * Require all items to be distinct
+   */
+  unique(): this {
+    this.schema.unique = true;
+    return this;
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: ArrayMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): ArraySchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Record schema builder with fluent API
+ * Keys may be missing, so the inferred type is partial over the key type
+ */
+export class ZRecord<
+  K extends SchemaBuilder<string>,
+  V extends SchemaBuilder<any>,
+> extends SchemaBuilder<
+  Partial<
+    Record<
+      K extends SchemaBuilder<infer U> ? U : never,
+      V extends SchemaBuilder<infer U> ? U : never
+    >
+  >
+> {
+  protected schema: RecordSchema;
+
+  constructor(keys: K | undefined, values: V) {
+    super();
+    this.schema = {
+      type: "record",
+      keys: keys?.toJSON(),
+      values: values.toJSON(),
+    };
+  }
+
+  /**
+   * Set minimum number of entries
+   */
+  minEntries(count: number): this {
+    this.schema.min_entries = count;
+    return this;
+  }
+
+  /**
+   * Set maximum number of entries
+   */
+  maxEntries(count: number): this {
+    this.schema.max_entries = count;
+    return this;
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: RecordMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): RecordSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Output types of a tuple's positions
+ */
+type TupleOutput<T extends SchemaBuilder<any>[]> = {
+  [K in keyof T]: T[K] extends SchemaBuilder<infer U> ? U : never;
+};
+
+/**
+ * Tuple schema builder with fluent API
+ */
+export class ZTuple<
+  T extends SchemaBuilder<any>[],
+  R extends SchemaBuilder<any> | undefined = undefined,
+> extends SchemaBuilder<
+  R extends SchemaBuilder<infer U> ? [...TupleOutput<T>, ...U[]] : TupleOutput<T>
+> {
+  protected schema: TupleSchema;
+
+  constructor(items: T) {
+    super();
+    this.schema = {
+      type: "tuple",
+      items: items.map((item) => item.toJSON()),
+    };
+  }
+
+  /**
+   * Validate elements past the fixed positions against a schema
+   */
+  rest<S extends SchemaBuilder<any>>(schema: S): ZTuple<T, S> {
+    this.schema.rest = schema.toJSON();
+    return this as unknown as ZTuple<T, S>;
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: TupleMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): TupleSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Enum schema builder with fluent API
+ */
+export class ZEnum<T extends string | number> extends SchemaBuilder<T> {
+  protected schema: EnumSchema;
+
+  constructor(values: readonly T[]) {
+    super();
+    this.schema = {
+      type: "enum",
+      values: [...values],
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: EnumMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): EnumSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Literal schema builder with fluent API
+ */
+export class ZLiteral<
+  T extends string | number | boolean | null,
+> extends SchemaBuilder<T> {
+  protected schema: LiteralSchema;
+
+  constructor(value: T) {
+    super();
+    this.schema = {
+      type: "literal",
+      value,
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: LiteralMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): LiteralSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Union schema builder with fluent API
+ */
+export class ZUnion<T extends SchemaBuilder<any>[]> extends SchemaBuilder<
+  T[number] extends SchemaBuilder<infer U> ? U : never
+> {
+  protected schema: UnionSchema;
+
+  constructor(options: T) {
+    super();
+    this.schema = {
+      type: "union",
+      options: options.map((option) => option.toJSON()),
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: UnionMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): UnionSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Intersection of the output types of a list of builders
+ */
+type IntersectOutputs<T extends SchemaBuilder<any>[]> = T extends [
+  SchemaBuilder<infer H>,
+  ...infer R extends SchemaBuilder<any>[],
+]
+  ? H & IntersectOutputs<R>
+  : unknown;
+
+/**
+ * Intersection schema builder with fluent API
+ */
+export class ZIntersection<T extends SchemaBuilder<any>[]> extends SchemaBuilder<
+  IntersectOutputs<T>
+> {
+  protected schema: IntersectionSchema;
+
+  constructor(options: T) {
+    super();
+    this.schema = {
+      type: "intersection",
+      options: options.map((option) => option.toJSON()),
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: IntersectionMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): IntersectionSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Discriminated union schema builder with fluent API
+ */
+export class ZDiscriminatedUnion<
+  T extends (ZObject<any> | ZRef<any>)[],
+> extends SchemaBuilder<T[number] extends SchemaBuilder<infer U> ? U : never> {
+  protected schema: DiscriminatedUnionSchema;
+
+  constructor(discriminator: string, options: T) {
+    super();
+    this.schema = {
+      type: "discriminated_union",
+      discriminator,
+      options: options.map((option) => option.toJSON()),
+    };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Add custom error messages
+   */
+  messages(messages: DiscriminatedUnionMessages): this {
+    this.schema.messages = messages;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): DiscriminatedUnionSchema {
+    return { ...this.schema };
+  }
+}
+
+/**
+ * Reference to a named definition, for recursive schemas
+ * The output type cannot be inferred through the cycle, so pass it explicitly
+ */
+export class ZRef<T = unknown> extends SchemaBuilder<T> {
+  protected schema: RefSchema;
+
+  constructor(name: string) {
+    super();
+    this.schema = { type: "ref", name };
+  }
+
+  /**
+   * Add UI configuration
+   */
+  ui(config: UiConfig): this {
+    this.schema.ui = config;
+    return this;
+  }
+
+  /**
+   * Serialize to JSON for Rust validation
+   */
+  toJSON(): RefSchema {
     return { ...this.schema };
   }
 }
@@ -1,4 +1,41 @@
-import { ZString, ZNumber, ZBoolean, ZObject, SchemaBuilder } from "./builders";
+import {
+  ZString,
+  ZNumber,
+  ZDecimal,
+  ZBigInt,
+  ZDate,
+  ZDateTime,
+  ZBoolean,
+  ZObject,
+  ZArray,
+  ZRecord,
+  ZTuple,
+  ZEnum,
+  ZLiteral,
+  ZUnion,
+  ZDiscriminatedUnion,
+  ZIntersection,
+  ZComposed,
+  ZRef,
+  SchemaBuilder,
+} from "./builders";
+
+/**
+ * Record builder, overloaded so the key schema can be left out
+ */
+function record<V extends SchemaBuilder<any>>(values: V): ZRecord<ZString, V>;
+function record<K extends SchemaBuilder<string>, V extends SchemaBuilder<any>>(
+  keys: K,
+  values: V,
+): ZRecord<K, V>;
+function record(
+  keysOrValues: SchemaBuilder<any>,
+  values?: SchemaBuilder<any>,
+): ZRecord<SchemaBuilder<string>, SchemaBuilder<any>> {
+  return values === undefined
+    ? new ZRecord(undefined, keysOrValues)
+    : new ZRecord(keysOrValues as SchemaBuilder<string>, values);
+}
 
 /**
  * Main schema builder API (Rustica-style)
@@ -26,6 +63,34 @@ export const r = {
     return new ZNumber();
   },
 
+  /**
+   * Create an exact decimal schema
+   */
+  decimal(): ZDecimal {
+    return new ZDecimal();
+  },
+
+  /**
+   * Create a big integer schema
+   */
+  bigint(): ZBigInt {
+    return new ZBigInt();
+  },
+
+  /**
+   * Create a calendar date schema
+   */
+  date(): ZDate {
+    return new ZDate();
+  },
+
+  /**
+   * Create a date-time schema
+   */
+  datetime(): ZDateTime {
+    return new ZDateTime();
+  },
+
   /**
    * Create a boolean schema
    */
@@ -39,12 +104,102 @@ export const r = {
   object<T extends Record<string, SchemaBuilder<any>>>(shape: T): ZObject<T> {
     return new ZObject(shape);
   },
+
+  /**
+   * Create an array schema
+   */
+  array<T extends SchemaBuilder<any>>(items: T): ZArray<T> {
+    return new ZArray(items);
+  },
+
+  /**
+   * Create a record schema with dynamic keys
+   * Pass a key schema first to restrict keys, e.g. r.record(r.enum(["en", "de"]), r.string())
+   */
+  record,
+
+  /**
+   * Create a tuple schema with one schema per position
+   */
+  tuple<T extends SchemaBuilder<any>[]>(items: [...T]): ZTuple<T> {
+    return new ZTuple(items);
+  },
+
+  /**
+   * Create an enum schema accepting one of the given values
+   */
+  enum<const T extends string | number>(values: readonly T[]): ZEnum<T> {
+    return new ZEnum(values);
+  },
+
+  /**
+   * Create a literal schema accepting exactly one value
+   */
+  literal<const T extends string | number | boolean | null>(
+    value: T,
+  ): ZLiteral<T> {
+    return new ZLiteral(value);
+  },
+
+  /**
+   * Create a union schema accepting any of the given options
+   */
+  union<T extends SchemaBuilder<any>[]>(options: [...T]): ZUnion<T> {
+    return new ZUnion(options);
+  },
+
+  /**
+   * Create a union of object schemas selected by a tag field
+   * Options may be r.ref() to object schemas in the root definitions
+   */
+  discriminatedUnion<T extends (ZObject<any> | ZRef<any>)[]>(
+    discriminator: string,
+    options: [...T],
+  ): ZDiscriminatedUnion<T> {
+    return new ZDiscriminatedUnion(discriminator, options);
+  },
+
+  /**
+   * Create an intersection schema: the value must match every option
+   * Object outputs are merged, e.g. r.intersection([base, r.object({ id: r.string() })])
+   */
+  intersection<T extends SchemaBuilder<any>[]>(options: [...T]): ZIntersection<T> {
+    return new ZIntersection(options);
+  },
+
+  /**
+   * Reference a schema declared with .definitions() on the root schema
+   * e.g. r.ref<Comment>("Comment") inside the Comment definition itself
+   */
+  ref<T = unknown>(name: string): ZRef<T> {
+    return new ZRef<T>(name);
+  },
 };
 
 // Type inference utility
 export type Infer<T> = T extends SchemaBuilder<infer U> ? U : never;
 
 // Re-export builders for advanced usage
-export { ZString, ZNumber, ZBoolean, ZObject, SchemaBuilder };
+export {
+  ZString,
+  ZNumber,
+  ZDecimal,
+  ZBigInt,
+  ZDate,
+  ZDateTime,
+  ZBoolean,
+  ZObject,
+  ZArray,
+  ZRecord,
+  ZTuple,
+  ZEnum,
+  ZLiteral,
+  ZUnion,
+  ZDiscriminatedUnion,
+  ZIntersection,
+  ZComposed,
+  ZRef,
+  SchemaBuilder,
+};
 export type { UiConfig } from "./types";
 export * from "./types";
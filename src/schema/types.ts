@@ -14,7 +14,27 @@ export interface StringMessages {
   max?: string;
   email?: string;
   url?: string;
+  uuid?: string;
+  ulid?: string;
+  cuid?: string;
+  ipv4?: string;
+  ipv6?: string;
+  cidr?: string;
+  hostname?: string;
+  date?: string;
+  time?: string;
+  datetime?: string;
+  duration?: string;
+  base64?: string;
+  hex?: string;
+  semver?: string;
+  jwt?: string;
   pattern?: string;
+  starts_with?: string;
+  ends_with?: string;
+  includes?: string;
+  excludes?: string;
+  charset?: string;
 }
 
 export interface NumberMessages {
@@ -23,60 +43,432 @@ export interface NumberMessages {
   max?: string;
   integer?: string;
   positive?: string;
+  exclusive_min?: string;
+  exclusive_max?: string;
+  multiple_of?: string;
+  negative?: string;
+  non_negative?: string;
+  non_positive?: string;
+  finite?: string;
+  safe_integer?: string;
+  coercion_failed?: string;
+}
+
+export interface DecimalMessages {
+  invalid_type?: string;
+  precision?: string;
+  scale?: string;
+  min?: string;
+  max?: string;
+}
+
+export interface BigIntMessages {
+  invalid_type?: string;
+  min?: string;
+  max?: string;
+}
+
+export interface DateMessages {
+  invalid_type?: string;
+  invalid?: string;
+  offset?: string;
+  min?: string;
+  max?: string;
+  min_age?: string;
+  max_age?: string;
 }
 
 export interface BooleanMessages {
   invalid_type?: string;
+  coercion_failed?: string;
 }
 
 export interface ObjectMessages {
   invalid_type?: string;
   required?: string;
+  unrecognized_keys?: string;
+}
+
+/**
+ * Policy for object keys not declared in the shape
+ */
+export type UnknownKeys =
+  | "strip"
+  | "strict"
+  | "passthrough"
+  | { catchall: Schema };
+
+/**
+ * Presence modifiers shared by every schema node
+ */
+export interface Modifiers {
+  optional?: boolean;
+  nullable?: boolean;
+  default?: unknown;
+  coerce?: boolean;
+  /** Named schemas that ref nodes resolve to; only read on the root schema */
+  definitions?: Record<string, Schema>;
+}
+
+export interface ArrayMessages {
+  invalid_type?: string;
+  min?: string;
+  max?: string;
+  unique?: string;
+}
+
+export interface RecordMessages {
+  invalid_type?: string;
+  invalid_key?: string;
+  duplicate_key?: string;
+  min_entries?: string;
+  max_entries?: string;
+}
+
+export interface TupleMessages {
+  invalid_type?: string;
+  too_small?: string;
+  too_big?: string;
 }
 
-export interface StringSchema {
+export interface EnumMessages {
+  invalid_enum_value?: string;
+}
+
+export interface LiteralMessages {
+  invalid_literal?: string;
+}
+
+export interface UnionMessages {
+  invalid_union?: string;
+}
+
+export interface DiscriminatedUnionMessages {
+  invalid_type?: string;
+  invalid_union_discriminator?: string;
+}
+
+/**
+ * Timezone offsets accepted by a date-time
+ * "any" (default) requires Z or ±HH:MM, "local" forbids an offset
+ */
+export type DateTimeOffset = "any" | "utc" | "local" | "optional";
+
+/**
+ * Unicode normalization form for string input
+ */
+export type Normalization = "nfc" | "nfkc";
+
+/**
+ * Unit string min/max lengths are measured in
+ * "utf16" matches JavaScript's String length and the maxlength attribute
+ */
+export type LengthUnit = "code_points" | "graphemes" | "utf16";
+
+/**
+ * Named character class; letter classes other than "letter" are ASCII-only
+ */
+export type CharClass =
+  | "alpha"
+  | "lower"
+  | "upper"
+  | "digit"
+  | "alphanumeric"
+  | "hex"
+  | "letter"
+  | "whitespace"
+  | "punctuation"
+  | "ascii";
+
+/**
+ * Allowed characters: any of the classes plus the listed characters
+ */
+export interface Charset {
+  classes?: CharClass[];
+  chars?: string;
+}
+
+export interface StringSchema extends Modifiers {
   type: "string";
   min?: number;
   max?: number;
+  length_unit?: LengthUnit;
   email?: boolean;
   url?: boolean;
+  schemes?: string[];
+  require_tld?: boolean;
+  allow_idn?: boolean;
+  allow_ip?: boolean;
+  max_length?: number;
+  uuid?: boolean;
+  ulid?: boolean;
+  cuid?: boolean;
+  ipv4?: boolean;
+  ipv6?: boolean;
+  cidr?: boolean;
+  hostname?: boolean;
+  date?: boolean;
+  time?: boolean;
+  datetime?: boolean;
+  duration?: boolean;
+  base64?: boolean;
+  hex?: boolean;
+  semver?: boolean;
+  jwt?: boolean;
+  offset?: DateTimeOffset;
   pattern?: string;
+  flags?: string;
+  starts_with?: string;
+  ends_with?: string;
+  includes?: string;
+  excludes?: string;
+  charset?: Charset;
+  trim?: boolean;
+  to_lower_case?: boolean;
+  to_upper_case?: boolean;
+  normalize?: Normalization;
   ui?: UiConfig;
   messages?: StringMessages;
 }
 
-export interface NumberSchema {
+export interface NumberSchema extends Modifiers {
   type: "number";
   min?: number;
   max?: number;
+  exclusive_min?: number;
+  exclusive_max?: number;
+  multiple_of?: number;
   integer?: boolean;
   positive?: boolean;
+  negative?: boolean;
+  non_negative?: boolean;
+  non_positive?: boolean;
+  finite?: boolean;
+  safe_integer?: boolean;
   ui?: UiConfig;
   messages?: NumberMessages;
 }
 
-export interface BooleanSchema {
+/**
+ * Exact decimal: accepts numbers and numeric strings and checks their digits
+ * Send amounts as strings to keep digits a JavaScript number would round
+ */
+export interface DecimalSchema extends Modifiers {
+  type: "decimal";
+  precision?: number;
+  scale?: number;
+  min?: string | number;
+  max?: string | number;
+  ui?: UiConfig;
+  messages?: DecimalMessages;
+}
+
+/**
+ * Integer of any size, e.g. 64-bit IDs sent as strings
+ */
+export interface BigIntSchema extends Modifiers {
+  type: "bigint";
+  min?: string | number;
+  max?: string | number;
+  ui?: UiConfig;
+  messages?: BigIntMessages;
+}
+
+/**
+ * Date or date-time bound: an ISO 8601 value, or "now" optionally shifted by
+ * a signed ISO 8601 duration such as "now+P1D" or "now-PT2H"
+ */
+export type DateBound = string;
+
+/**
+ * Calendar date as YYYY-MM-DD; "now" in bounds means today at utc_offset
+ */
+export interface DateSchema extends Modifiers {
+  type: "date";
+  min?: DateBound;
+  max?: DateBound;
+  min_age?: number;
+  max_age?: number;
+  epoch_millis?: boolean;
+  utc_offset?: string;
+  ui?: UiConfig;
+  messages?: DateMessages;
+}
+
+/**
+ * RFC 3339 date-time compared as an instant
+ * utc_offset ("Z" or "±HH:MM") is assumed for input without an offset
+ */
+export interface DateTimeSchema extends Modifiers {
+  type: "datetime";
+  min?: DateBound;
+  max?: DateBound;
+  min_age?: number;
+  max_age?: number;
+  epoch_millis?: boolean;
+  offset?: DateTimeOffset;
+  utc_offset?: string;
+  ui?: UiConfig;
+  messages?: DateMessages;
+}
+
+export interface BooleanSchema extends Modifiers {
   type: "boolean";
   ui?: UiConfig;
   messages?: BooleanMessages;
 }
 
-export interface ObjectSchema {
+export interface ObjectSchema extends Modifiers {
   type: "object";
   shape: Record<string, Schema>;
+  unknown_keys?: UnknownKeys;
   ui?: UiConfig;
   messages?: ObjectMessages;
 }
 
-export type Schema = StringSchema | NumberSchema | BooleanSchema | ObjectSchema;
+export interface ArraySchema extends Modifiers {
+  type: "array";
+  items: Schema;
+  min?: number;
+  max?: number;
+  unique?: boolean;
+  ui?: UiConfig;
+  messages?: ArrayMessages;
+}
+
+/**
+ * Object with dynamic keys; each key is validated as a string against keys
+ */
+export interface RecordSchema extends Modifiers {
+  type: "record";
+  keys?: Schema;
+  values: Schema;
+  min_entries?: number;
+  max_entries?: number;
+  ui?: UiConfig;
+  messages?: RecordMessages;
+}
+
+/**
+ * Fixed-length array typed by position; rest validates extra trailing items
+ */
+export interface TupleSchema extends Modifiers {
+  type: "tuple";
+  items: Schema[];
+  rest?: Schema;
+  ui?: UiConfig;
+  messages?: TupleMessages;
+}
+
+export interface EnumSchema extends Modifiers {
+  type: "enum";
+  values: (string | number)[];
+  ui?: UiConfig;
+  messages?: EnumMessages;
+}
+
+export interface LiteralSchema extends Modifiers {
+  type: "literal";
+  value: string | number | boolean | null;
+  ui?: UiConfig;
+  messages?: LiteralMessages;
+}
+
+export interface UnionSchema extends Modifiers {
+  type: "union";
+  options: Schema[];
+  ui?: UiConfig;
+  messages?: UnionMessages;
+}
+
+export interface DiscriminatedUnionSchema extends Modifiers {
+  type: "discriminated_union";
+  discriminator: string;
+  options: (ObjectSchema | RefSchema)[];
+  ui?: UiConfig;
+  messages?: DiscriminatedUnionMessages;
+}
+
+/**
+ * Reference to a schema in the root definitions table, for recursive shapes
+ */
+export interface RefSchema extends Modifiers {
+  type: "ref";
+  name: string;
+  ui?: UiConfig;
+}
+
+export interface IntersectionMessages {
+  invalid_intersection?: string;
+}
+
+/**
+ * Value must match every option; the parsed outputs are merged
+ */
+export interface IntersectionSchema extends Modifiers {
+  type: "intersection";
+  options: Schema[];
+  ui?: UiConfig;
+  messages?: IntersectionMessages;
+}
+
+/**
+ * Combinator applied to the base schema of a compose node
+ */
+export type CompositionStep =
+  | { op: "extend"; shape: Record<string, Schema> }
+  | { op: "merge"; schema: Schema }
+  | { op: "pick"; keys: string[] }
+  | { op: "omit"; keys: string[] }
+  | { op: "partial" }
+  | { op: "deep_partial" }
+  | { op: "required" };
+
+/**
+ * Object schema derived from a base by combinators, expanded by the validator
+ */
+export interface ComposeSchema extends Modifiers {
+  type: "compose";
+  schema: Schema;
+  steps: CompositionStep[];
+  ui?: UiConfig;
+}
+
+export type Schema =
+  | StringSchema
+  | NumberSchema
+  | DecimalSchema
+  | BigIntSchema
+  | DateSchema
+  | DateTimeSchema
+  | BooleanSchema
+  | ObjectSchema
+  | ArraySchema
+  | RecordSchema
+  | TupleSchema
+  | EnumSchema
+  | LiteralSchema
+  | UnionSchema
+  | DiscriminatedUnionSchema
+  | IntersectionSchema
+  | ComposeSchema
+  | RefSchema;
 
 export interface ValidationError {
   path: string[];
   code: string;
   message: string;
+  /** Structured values behind the message, e.g. `{ minimum: 5, actual: 2 }` */
+  params?: Record<string, unknown>;
 }
 
 export interface ValidationResult {
   success: boolean;
   errors?: ValidationError[];
 }
+
+export interface ParseResult<T = unknown> {
+  success: boolean;
+  data?: T;
+  errors?: ValidationError[];
+}
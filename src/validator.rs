@@ -1,14 +1,168 @@
-use crate::schema::{Schema, ValidationError, ValidationResult};
+use crate::date::{self, DateBound, UtcOffset};
+use crate::decimal::Numeric;
+use crate::format::{self, Format};
+use crate::locale;
+use crate::lookup::Lookups;
+use crate::pattern::PatternCache;
+use crate::schema::{
+    ArraySchema, BigIntSchema, DateMessages, DateTimeSchema, DecimalSchema,
+    DiscriminatedUnionSchema, IntersectionSchema, LengthUnit, Normalization, NumberSchema,
+    ObjectSchema, RecordSchema, RefSchema, Schema, SchemaError, StringSchema, TupleSchema, UiConfig,
+    UnionSchema, UnknownKeys, ValidationError, ValidationResult,
+};
+use indexmap::IndexMap;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use unicode_normalization::{is_nfc, is_nfkc, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Largest integer a float holds exactly, JavaScript's `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Default reference nesting limit: deeper than comment threads or category
+/// trees get in practice, shallow enough for a WebAssembly stack
+const DEFAULT_MAX_DEPTH: usize = 32;
 
 /// Main validator that processes schema against JSON values
 pub struct Validator;
 
+/// Options that apply to a whole validation run
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+    /// Locale whose catalog supplies default error messages
+    pub locale: String,
+    /// Convert string input on every node that does not set `coerce` itself
+    pub coerce: bool,
+    /// Current time in milliseconds since the Unix epoch for relative date
+    /// bounds and ages; the system clock when unset
+    pub now: Option<i64>,
+    /// Most references that may be expanded inside one another, bounding
+    /// recursion on deeply nested input
+    pub max_depth: usize,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            locale: locale::DEFAULT_LOCALE.to_string(),
+            coerce: false,
+            now: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+/// Date or date-time input placed on the timeline
+#[derive(Clone, Copy)]
+enum DatePoint {
+    /// Days since the epoch
+    Date(i64),
+    /// Nanoseconds since the epoch
+    Instant(i128),
+}
+
+/// Range and age rules shared by date and date-time schemas
+struct DateRules<'a> {
+    min: Option<&'a DateBound>,
+    max: Option<&'a DateBound>,
+    min_age: Option<u32>,
+    max_age: Option<u32>,
+    offset: UtcOffset,
+    messages: Option<&'a DateMessages>,
+}
+
+/// State shared by every node of a single validation run
+struct Context<'a> {
+    patterns: &'a PatternCache,
+    /// Tables precomputed for a compiled schema
+    lookups: Option<&'a Lookups>,
+    options: &'a ValidationOptions,
+    /// Definitions of the root schema that references resolve to
+    definitions: &'a IndexMap<String, Schema>,
+    /// References currently being expanded
+    depth: Cell<usize>,
+}
+
+impl<'a> Context<'a> {
+    fn new(
+        root: &'a Schema,
+        patterns: &'a PatternCache,
+        lookups: Option<&'a Lookups>,
+        options: &'a ValidationOptions,
+    ) -> Self {
+        Self {
+            patterns,
+            lookups,
+            options,
+            definitions: &root.modifiers().definitions,
+            depth: Cell::new(0),
+        }
+    }
+
+    /// Custom message for a node, or the locale's default for the error code
+    fn message(&self, custom: Option<&str>, code: &str) -> String {
+        match custom {
+            Some(custom) => custom.to_string(),
+            None => locale::template(&self.options.locale, code),
+        }
+    }
+
+    /// Schema a reference stands for, or the schema itself
+    fn resolve<'s>(&'s self, schema: &'s Schema) -> &'s Schema {
+        let resolved = match (schema, self.lookups) {
+            (Schema::Ref(reference), Some(lookups)) => lookups.target(self.definitions, &reference.name),
+            _ => schema.resolve(self.definitions),
+        };
+        resolved.unwrap_or(schema)
+    }
+
+    /// Option of a discriminated union selected by the value's tag
+    fn discriminated_option<'s>(
+        &'s self,
+        union: &'s DiscriminatedUnionSchema,
+        value: &Value,
+    ) -> Option<&'s Schema> {
+        let tag = value.as_object()?.get(&union.discriminator)?;
+        match self.lookups.and_then(|lookups| lookups.option(union, tag)) {
+            Some(option) => option,
+            None => Validator::discriminated_option(self.definitions, union, value),
+        }
+    }
+
+    /// Current instant in nanoseconds since the epoch
+    fn now(&self) -> i128 {
+        date::instant_from_millis(self.options.now.unwrap_or_else(date::current_millis))
+    }
+
+    /// Whether string input is converted for a node
+    fn coerces(&self, schema: &Schema) -> bool {
+        let target = self.resolve(schema);
+        // A reference can turn coercion on for the node it refers to, which
+        // otherwise keeps its own flag once the reference is followed
+        (!std::ptr::eq(target, schema) && schema.modifiers().coerce == Some(true))
+            || target.modifiers().coerce.unwrap_or(self.options.coerce)
+    }
+
+    /// Whether a value counts as missing: an empty string on a coercing node
+    fn is_blank(&self, schema: &Schema, value: &Value) -> bool {
+        value.as_str() == Some("") && self.coerces(schema)
+    }
+}
+
 impl Validator {
     /// Validate a value against a schema
     pub fn validate(schema: &Schema, value: &Value) -> ValidationResult {
-        Self::validate_with_path(schema, value, &[])
+        Self::validate_with_options(schema, value, &ValidationOptions::default())
+    }
+
+    /// Validate a value and return the cleaned output
+    ///
+    /// The output has defaults applied and unknown object keys removed.
+    pub fn parse(schema: &Schema, value: &Value) -> Result<Value, Vec<ValidationError>> {
+        Self::parse_with_options(schema, value, &ValidationOptions::default())
     }
 
     /// Validate a value at a specific path in the schema
@@ -16,602 +170,3358 @@ impl Validator {
         schema: &Schema,
         value: &Value,
         path: &[String],
+    ) -> ValidationResult {
+        Self::validate_at_path_with_options(schema, value, path, &ValidationOptions::default())
+    }
+
+    /// Validate a value with explicit options
+    ///
+    /// Patterns are compiled for this call only; a `CompiledSchema` keeps
+    /// them across calls.
+    pub fn validate_with_options(
+        schema: &Schema,
+        value: &Value,
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        Self::validate_with_patterns(schema, value, &PatternCache::default(), None, options)
+    }
+
+    /// Parse a value with explicit options
+    pub fn parse_with_options(
+        schema: &Schema,
+        value: &Value,
+        options: &ValidationOptions,
+    ) -> Result<Value, Vec<ValidationError>> {
+        Self::parse_with_patterns(schema, value, &PatternCache::default(), None, options)
+    }
+
+    /// Validate a value at a path with explicit options
+    pub fn validate_at_path_with_options(
+        schema: &Schema,
+        value: &Value,
+        path: &[String],
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        Self::validate_at_path_with_patterns(schema, value, path, &PatternCache::default(), None, options)
+    }
+
+    /// Validate using a caller-owned pattern cache and optional lookup tables
+    pub(crate) fn validate_with_patterns(
+        schema: &Schema,
+        value: &Value,
+        patterns: &PatternCache,
+        lookups: Option<&Lookups>,
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        let ctx = Context::new(schema, patterns, lookups, options);
+        Self::validate_with_path(&ctx, schema, value, &[])
+    }
+
+    /// Parse using a caller-owned pattern cache and optional lookup tables
+    pub(crate) fn parse_with_patterns(
+        schema: &Schema,
+        value: &Value,
+        patterns: &PatternCache,
+        lookups: Option<&Lookups>,
+        options: &ValidationOptions,
+    ) -> Result<Value, Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let ctx = Context::new(schema, patterns, lookups, options);
+        let output = Self::parse_with_path(&ctx, schema, value, &[], &mut errors);
+
+        if errors.is_empty() {
+            Ok(output)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validate at a path using a caller-owned pattern cache and optional
+    /// lookup tables
+    pub(crate) fn validate_at_path_with_patterns(
+        schema: &Schema,
+        value: &Value,
+        path: &[String],
+        patterns: &PatternCache,
+        lookups: Option<&Lookups>,
+        options: &ValidationOptions,
     ) -> ValidationResult {
         if path.is_empty() {
-            return Self::validate(schema, value);
+            return Self::validate_with_patterns(schema, value, patterns, lookups, options);
         }
 
+        let ctx = Context::new(schema, patterns, lookups, options);
+
         // Navigate to the target schema
-        let target_schema = Self::navigate_schema(schema, path)?;
+        let target_schema = Self::navigate_schema(ctx.definitions, schema, value, path)?;
         
         // Navigate to the target value
-        let target_value = Self::navigate_value(value, path);
-        
-        Self::validate_with_path(target_schema, target_value, path)
+        let modifiers = target_schema.modifiers();
+        let target_value = Self::navigate_value(value, path)
+            .filter(|target_value| !ctx.is_blank(target_schema, target_value));
+        let target_value = match target_value {
+            Some(target_value) => target_value,
+            None => match &modifiers.default {
+                Some(default) => default,
+                None if modifiers.is_optional() => return Ok(()),
+                None => {
+                    // A missing or blank object field is reported as `validate` does
+                    if let Some(object) = Self::parent_object(&ctx, schema, value, path) {
+                        let key = &path[path.len() - 1];
+                        return Err(vec![Self::required_error(&ctx, object, key, target_schema, path.to_vec())]);
+                    }
+                    &Value::Null
+                }
+            },
+        };
+
+        Self::validate_with_path(&ctx, target_schema, target_value, path)
+    }
+
+    /// Object schema that declares the last segment of a path as a field
+    fn parent_object<'a>(
+        ctx: &Context<'a>,
+        schema: &'a Schema,
+        value: &Value,
+        path: &[String],
+    ) -> Option<&'a ObjectSchema> {
+        let (key, parent_path) = path.split_last()?;
+        let parent = Self::navigate_schema(ctx.definitions, schema, value, parent_path).ok()?;
+        let parent_value = Self::navigate_value(value, parent_path);
+        match Self::select_branch(ctx.definitions, parent, parent_value, key, ctx.definitions.len()) {
+            Schema::Object(object) if object.shape.contains_key(key) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// `required` error for a field missing from an object
+    fn required_error(
+        ctx: &Context,
+        object: &ObjectSchema,
+        key: &str,
+        field_schema: &Schema,
+        field_path: Vec<String>,
+    ) -> ValidationError {
+        let msg = ctx.message(
+            object.messages.as_ref().and_then(|m| m.required.as_deref()),
+            "required",
+        );
+        ValidationError::new(field_path, "required", msg)
+            .with_param("key", key)
+            .with_param("expected", ctx.resolve(field_schema).type_name())
+            .with_param("received", "undefined")
+            .interpolate(field_schema.label(), None)
     }
 
     /// Internal validation with path tracking
     fn validate_with_path(
+        ctx: &Context,
         schema: &Schema,
         value: &Value,
         path: &[String],
     ) -> ValidationResult {
         let mut errors = Vec::new();
+        Self::parse_with_path(ctx, schema, value, path, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validate a value with path tracking and build its output
+    fn parse_with_path(
+        ctx: &Context,
+        schema: &Schema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Value {
+        if value.is_null() && schema.modifiers().is_nullable() {
+            return Value::Null;
+        }
+
+        let target = ctx.resolve(schema);
+        if let (Some(s), Schema::Number(_) | Schema::Boolean(_)) = (value.as_str(), target) {
+            if ctx.coerces(schema) {
+                return match Self::coerce(target, s) {
+                    Some(coerced) => Self::parse_with_path(ctx, schema, &coerced, path, errors),
+                    None => {
+                        Self::push_coercion_error(ctx, target, value, path, errors);
+                        value.clone()
+                    }
+                };
+            }
+        }
 
         match schema {
-            Schema::String { min, max, email, url, pattern, messages, .. } => {
+            Schema::String(string) => {
                 if let Some(s) = value.as_str() {
-                    Self::validate_string(s, *min, *max, *email, *url, pattern.as_deref(), messages, path, &mut errors);
+                    // Checks run against the transformed string, which is also the output
+                    let output = Value::from(Self::transform_string(string, s));
+                    if let Value::String(s) = &output {
+                        Self::validate_string(ctx, s, &output, string, path, errors);
+                    }
+                    output
+                } else {
+                    let custom = string.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                    value.clone()
+                }
+            }
+            Schema::Number(number) => {
+                if let Some(n) = Self::float_value(value) {
+                    Self::validate_number(ctx, n, value, number, path, errors);
+                } else {
+                    let custom = number.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                }
+                value.clone()
+            }
+            Schema::Decimal(decimal) => {
+                if let Some(n) = Numeric::from_value(value) {
+                    Self::validate_decimal(ctx, &n, value, decimal, path, errors);
                 } else {
-                    let msg = messages
-                        .as_ref()
-                        .and_then(|m| m.invalid_type.as_deref())
-                        .unwrap_or("Expected string");
-                    errors.push(ValidationError::new(
-                        path.to_vec(),
-                        "invalid_type",
-                        msg,
-                    ));
+                    let custom = decimal.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
                 }
+                value.clone()
             }
-            Schema::Number { min, max, integer, positive, messages, .. } => {
-                if let Some(n) = value.as_f64() {
-                    Self::validate_number(n, *min, *max, *integer, *positive, messages, path, &mut errors);
+            Schema::BigInt(bigint) => {
+                if let Some(n) = Numeric::from_value(value).filter(Numeric::is_integer) {
+                    Self::validate_bigint(ctx, &n, value, bigint, path, errors);
                 } else {
-                    let msg = messages
-                        .as_ref()
-                        .and_then(|m| m.invalid_type.as_deref())
-                        .unwrap_or("Expected number");
-                    errors.push(ValidationError::new(
-                        path.to_vec(),
-                        "invalid_type",
-                        msg,
-                    ));
+                    let custom = bigint.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
                 }
+                value.clone()
+            }
+            Schema::Date(date_schema) => {
+                let offset = date_schema.utc_offset.unwrap_or_default();
+                let point = match value {
+                    Value::String(s) => date::parse_date(s).map(DatePoint::Date).ok_or("date.invalid"),
+                    Value::Number(n) if date_schema.epoch_millis == Some(true) => n
+                        .as_i64()
+                        .map(|millis| DatePoint::Date(offset.date_of(date::instant_from_millis(millis))))
+                        .ok_or("date.invalid"),
+                    _ => Err("invalid_type"),
+                };
+                let rules = DateRules {
+                    min: date_schema.min.as_ref(),
+                    max: date_schema.max.as_ref(),
+                    min_age: date_schema.min_age,
+                    max_age: date_schema.max_age,
+                    offset,
+                    messages: date_schema.messages.as_ref(),
+                };
+                Self::check_date(ctx, point, &rules, schema, value, path, errors);
+                value.clone()
             }
-            Schema::Boolean { messages, .. } => {
+            Schema::DateTime(datetime) => {
+                let offset = datetime.utc_offset.unwrap_or_default();
+                let point = match value {
+                    Value::String(s) => Self::read_datetime(s, datetime, offset),
+                    Value::Number(n) if datetime.epoch_millis == Some(true) => n
+                        .as_i64()
+                        .map(|millis| DatePoint::Instant(date::instant_from_millis(millis)))
+                        .ok_or("date.invalid"),
+                    _ => Err("invalid_type"),
+                };
+                let rules = DateRules {
+                    min: datetime.min.as_ref(),
+                    max: datetime.max.as_ref(),
+                    min_age: datetime.min_age,
+                    max_age: datetime.max_age,
+                    offset,
+                    messages: datetime.messages.as_ref(),
+                };
+                Self::check_date(ctx, point, &rules, schema, value, path, errors);
+                value.clone()
+            }
+            Schema::Boolean(boolean) => {
                 if !value.is_boolean() {
-                    let msg = messages
-                        .as_ref()
-                        .and_then(|m| m.invalid_type.as_deref())
-                        .unwrap_or("Expected boolean");
-                    errors.push(ValidationError::new(
-                        path.to_vec(),
-                        "invalid_type",
-                        msg,
-                    ));
+                    let custom = boolean.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                }
+                value.clone()
+            }
+            Schema::Object(object) => {
+                if let Some(obj) = value.as_object() {
+                    Value::Object(Self::validate_object(ctx, object, obj, path, errors))
+                } else {
+                    let custom = object.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                    value.clone()
+                }
+            }
+            Schema::Array(array) => {
+                if let Some(arr) = value.as_array() {
+                    Value::Array(Self::validate_array(ctx, array, arr, value, path, errors))
+                } else {
+                    let custom = array.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                    value.clone()
                 }
             }
-            Schema::Object { shape, messages, .. } => {
+            Schema::Record(record) => {
                 if let Some(obj) = value.as_object() {
-                    Self::validate_object(shape, obj, messages, path, &mut errors);
+                    Value::Object(Self::validate_record(ctx, record, obj, value, path, errors))
+                } else {
+                    let custom = record.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                    value.clone()
+                }
+            }
+            Schema::Tuple(tuple) => {
+                if let Some(arr) = value.as_array() {
+                    Value::Array(Self::validate_tuple(ctx, tuple, arr, value, path, errors))
+                } else {
+                    let custom = tuple.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, schema.type_name(), value, custom, schema.label()));
+                    value.clone()
+                }
+            }
+            Schema::Enum(enumeration) => {
+                if !enumeration.values.iter().any(|allowed| Self::values_equal(allowed, value)) {
+                    let msg = ctx.message(
+                        enumeration.messages.as_ref().and_then(|m| m.invalid_enum_value.as_deref()),
+                        "invalid_enum_value",
+                    );
+                    errors.push(
+                        ValidationError::new(path.to_vec(), "invalid_enum_value", msg)
+                            .with_param("options", enumeration.values.clone())
+                            .with_param("received", value.clone())
+                            .interpolate(schema.label(), Some(value)),
+                    );
+                }
+                value.clone()
+            }
+            Schema::Literal(literal) => {
+                if !Self::values_equal(&literal.value, value) {
+                    let msg = ctx.message(
+                        literal.messages.as_ref().and_then(|m| m.invalid_literal.as_deref()),
+                        "invalid_literal",
+                    );
+                    errors.push(
+                        ValidationError::new(path.to_vec(), "invalid_literal", msg)
+                            .with_param("expected", literal.value.clone())
+                            .with_param("received", value.clone())
+                            .interpolate(schema.label(), Some(value)),
+                    );
+                }
+                value.clone()
+            }
+            Schema::Union(union) => Self::validate_union(ctx, union, value, path, errors),
+            Schema::DiscriminatedUnion(union) => {
+                if value.is_object() {
+                    Self::validate_discriminated_union(ctx, union, value, path, errors)
                 } else {
-                    let msg = messages
-                        .as_ref()
-                        .and_then(|m| m.invalid_type.as_deref())
-                        .unwrap_or("Expected object");
-                    errors.push(ValidationError::new(
-                        path.to_vec(),
-                        "invalid_type",
-                        msg,
-                    ));
+                    let custom = union.messages.as_ref().and_then(|m| m.invalid_type.as_deref());
+                    errors.push(Self::invalid_type(ctx, path, "object", value, custom, schema.label()));
+                    value.clone()
                 }
             }
+            Schema::Intersection(intersection) => {
+                Self::validate_intersection(ctx, intersection, value, path, errors)
+            }
+            Schema::Ref(reference) => Self::parse_ref(ctx, reference, value, path, errors),
         }
+    }
 
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+    /// `invalid_type` error for a value of the wrong JSON type
+    ///
+    /// `custom` is the node's own message for the code, if it sets one.
+    fn invalid_type(
+        ctx: &Context,
+        path: &[String],
+        expected: &str,
+        value: &Value,
+        custom: Option<&str>,
+        label: Option<&str>,
+    ) -> ValidationError {
+        let msg = ctx.message(custom, "invalid_type");
+        ValidationError::new(path.to_vec(), "invalid_type", msg)
+            .with_param("expected", expected)
+            .with_param("received", Self::type_name(value))
+            .interpolate(label, Some(value))
+    }
+
+    /// Validate against the definition a reference names
+    ///
+    /// Each expansion counts toward `max_depth`; past it the value is
+    /// rejected rather than descended into, so input nesting cannot exhaust
+    /// the stack.
+    fn parse_ref(
+        ctx: &Context,
+        schema: &RefSchema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Value {
+        let Some(target) = ctx.definitions.get(&schema.name) else {
+            errors.push(SchemaError::UnknownReference {
+                path: path.to_vec(),
+                name: schema.name.clone(),
+            }.into());
+            return value.clone();
+        };
+
+        let depth = ctx.depth.get();
+        if depth >= ctx.options.max_depth {
+            let msg = ctx.message(None, "too_deep");
+            errors.push(
+                ValidationError::new(path.to_vec(), "too_deep", msg)
+                    .with_param("maximum", ctx.options.max_depth)
+                    .interpolate(Self::label(&schema.ui), Some(value)),
+            );
+            return value.clone();
+        }
+
+        ctx.depth.set(depth + 1);
+        let output = Self::parse_with_path(ctx, target, value, path, errors);
+        ctx.depth.set(depth);
+        output
+    }
+
+    /// Convert string input to a number or boolean, `None` if it does not parse
+    fn coerce(schema: &Schema, s: &str) -> Option<Value> {
+        let s = s.trim();
+
+        match schema {
+            Schema::Number(_) => s
+                .parse::<i64>()
+                .map(Value::from)
+                .ok()
+                .or_else(|| s.parse::<f64>().ok().filter(|n| n.is_finite()).map(Value::from)),
+            Schema::Boolean(_) => match s.to_ascii_lowercase().as_str() {
+                "true" | "on" | "1" => Some(Value::Bool(true)),
+                "false" | "off" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Report string input that could not be converted to the node's type
+    fn push_coercion_error(
+        ctx: &Context,
+        schema: &Schema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let custom = match schema {
+            Schema::Number(number) => number.messages.as_ref().and_then(|m| m.coercion_failed.as_deref()),
+            Schema::Boolean(boolean) => boolean.messages.as_ref().and_then(|m| m.coercion_failed.as_deref()),
+            _ => None,
+        };
+        let msg = ctx.message(custom, "coercion_failed");
+        errors.push(
+            ValidationError::new(path.to_vec(), "coercion_failed", msg)
+                .with_param("expected", schema.type_name())
+                .with_param("received", value.clone())
+                .interpolate(schema.label(), Some(value)),
+        );
+    }
+
+    /// Apply a string schema's normalization, trimming and case transforms
+    fn transform_string<'a>(schema: &StringSchema, s: &'a str) -> Cow<'a, str> {
+        let mut s = Cow::Borrowed(s);
+
+        if let Some(form) = schema.normalize {
+            let normalized = match form {
+                Normalization::Nfc if !is_nfc(&s) => Some(s.nfc().collect()),
+                Normalization::Nfkc if !is_nfkc(&s) => Some(s.nfkc().collect()),
+                _ => None,
+            };
+            if let Some(normalized) = normalized {
+                s = Cow::Owned(normalized);
+            }
+        }
+
+        if schema.trim == Some(true) {
+            s = match s {
+                Cow::Borrowed(b) => Cow::Borrowed(b.trim()),
+                Cow::Owned(o) => Cow::Owned(o.trim().to_string()),
+            };
+        }
+
+        if schema.to_lower_case == Some(true) {
+            s = Cow::Owned(s.to_lowercase());
+        }
+
+        if schema.to_upper_case == Some(true) {
+            s = Cow::Owned(s.to_uppercase());
+        }
+
+        s
+    }
+
+    /// Length of a string in the given unit
+    fn string_length(s: &str, unit: LengthUnit) -> usize {
+        match unit {
+            LengthUnit::CodePoints => s.chars().count(),
+            LengthUnit::Graphemes => s.graphemes(true).count(),
+            LengthUnit::Utf16 => s.encode_utf16().count(),
         }
     }
 
     /// Validate string constraints
     fn validate_string(
+        ctx: &Context,
         s: &str,
-        min: Option<usize>,
-        max: Option<usize>,
-        email: Option<bool>,
-        url: Option<bool>,
-        pattern: Option<&str>,
-        messages: &Option<crate::schema::StringMessages>,
+        value: &Value,
+        schema: &StringSchema,
         path: &[String],
         errors: &mut Vec<ValidationError>,
     ) {
-        if let Some(min_len) = min {
-            if s.len() < min_len {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.min.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("String must be at least {} characters", min_len));
-                errors.push(ValidationError::new(
-                    path.to_vec(),
+        let messages = &schema.messages;
+        let label = Self::label(&schema.ui);
+        let unit = schema.length_unit.unwrap_or_default();
+        let length = if schema.min.is_some() || schema.max.is_some() {
+            Self::string_length(s, unit)
+        } else {
+            0
+        };
+
+        if let Some(min_len) = schema.min {
+            if length < min_len {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.min.as_deref()),
                     "string.min",
-                    msg,
-                ));
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.min", msg)
+                        .with_param("minimum", min_len)
+                        .with_param("actual", length)
+                        .with_param("unit", unit.as_str())
+                        .interpolate(label, Some(value)),
+                );
             }
         }
 
-        if let Some(max_len) = max {
-            if s.len() > max_len {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.max.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("String must be at most {} characters", max_len));
-                errors.push(ValidationError::new(
-                    path.to_vec(),
+        if let Some(max_len) = schema.max {
+            if length > max_len {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.max.as_deref()),
                     "string.max",
-                    msg,
-                ));
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.max", msg)
+                        .with_param("maximum", max_len)
+                        .with_param("actual", length)
+                        .with_param("unit", unit.as_str())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if schema.email == Some(true) {
+            if let Err(reason) = format::check_email(s, schema) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.email.as_deref()),
+                    "string.email",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.email", msg)
+                        .with_param("validation", "email")
+                        .with_param("reason", reason)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if schema.url == Some(true) {
+            if let Err(reason) = format::check_url(s, schema) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.url.as_deref()),
+                    "string.url",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.url", msg)
+                        .with_param("validation", "url")
+                        .with_param("reason", reason)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        for format in Format::ALL {
+            if format.is_enabled(schema) && !format.matches(s, schema) {
+                let code = format!("string.{}", format.name());
+                let msg = ctx.message(messages.as_ref().and_then(|m| format.message(m)), &code);
+                errors.push(
+                    ValidationError::new(path.to_vec(), code, msg)
+                        .with_param("validation", format.name())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(prefix) = schema.starts_with.as_deref() {
+            if !s.starts_with(prefix) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.starts_with.as_deref()),
+                    "string.starts_with",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.starts_with", msg)
+                        .with_param("prefix", prefix)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(suffix) = schema.ends_with.as_deref() {
+            if !s.ends_with(suffix) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.ends_with.as_deref()),
+                    "string.ends_with",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.ends_with", msg)
+                        .with_param("suffix", suffix)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(substring) = schema.includes.as_deref() {
+            if !s.contains(substring) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.includes.as_deref()),
+                    "string.includes",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.includes", msg)
+                        .with_param("substring", substring)
+                        .interpolate(label, Some(value)),
+                );
             }
         }
 
-        if email == Some(true) && !Self::is_valid_email(s) {
-            let msg = messages
-                .as_ref()
-                .and_then(|m| m.email.as_deref())
-                .unwrap_or("Invalid email address");
-            errors.push(ValidationError::new(
-                path.to_vec(),
-                "string.email",
-                msg,
-            ));
+        if let Some(substring) = schema.excludes.as_deref() {
+            if !substring.is_empty() && s.contains(substring) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.excludes.as_deref()),
+                    "string.excludes",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.excludes", msg)
+                        .with_param("substring", substring)
+                        .interpolate(label, Some(value)),
+                );
+            }
         }
 
-        if url == Some(true) && !Self::is_valid_url(s) {
-            let msg = messages
-                .as_ref()
-                .and_then(|m| m.url.as_deref())
-                .unwrap_or("Invalid URL");
-            errors.push(ValidationError::new(
-                path.to_vec(),
-                "string.url",
-                msg,
-            ));
+        if let Some(charset) = &schema.charset {
+            let mut invalid: Vec<String> = Vec::new();
+            for c in s.chars().filter(|&c| !charset.contains(c)) {
+                let c = c.to_string();
+                if !invalid.contains(&c) {
+                    invalid.push(c);
+                }
+            }
+            if !invalid.is_empty() {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.charset.as_deref()),
+                    "string.charset",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "string.charset", msg)
+                        .with_param("characters", invalid)
+                        .interpolate(label, Some(value)),
+                );
+            }
         }
 
-        if let Some(regex_pattern) = pattern {
-            // Basic pattern matching (in production, use regex crate)
-            if !s.contains(regex_pattern) {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.pattern.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("String does not match pattern: {}", regex_pattern));
-                errors.push(ValidationError::new(
-                    path.to_vec(),
-                    "string.pattern",
-                    msg,
-                ));
+        if let Some(regex_pattern) = schema.pattern.as_deref() {
+            match ctx.patterns.is_match(regex_pattern, schema.flags.as_deref(), s) {
+                Ok(true) => {}
+                Ok(false) => {
+                    let msg = ctx.message(
+                        messages.as_ref().and_then(|m| m.pattern.as_deref()),
+                        "string.pattern",
+                    );
+                    errors.push(
+                        ValidationError::new(path.to_vec(), "string.pattern", msg)
+                            .with_param("pattern", regex_pattern)
+                            .interpolate(label, Some(value)),
+                    );
+                }
+                Err(reason) => {
+                    errors.push(SchemaError::InvalidPattern {
+                        path: path.to_vec(),
+                        pattern: regex_pattern.to_string(),
+                        reason,
+                    }.into());
+                }
             }
         }
     }
 
     /// Validate number constraints
     fn validate_number(
+        ctx: &Context,
         n: f64,
-        min: Option<f64>,
-        max: Option<f64>,
-        integer: Option<bool>,
-        positive: Option<bool>,
-        messages: &Option<crate::schema::NumberMessages>,
+        value: &Value,
+        schema: &NumberSchema,
         path: &[String],
         errors: &mut Vec<ValidationError>,
     ) {
-        if let Some(min_val) = min {
+        let messages = schema.messages.as_ref();
+        let label = Self::label(&schema.ui);
+        let error = |custom: Option<&String>, code: &str| {
+            let msg = ctx.message(custom.map(String::as_str), code);
+            ValidationError::new(path.to_vec(), code, msg).with_param("actual", n)
+        };
+
+        if let Some(min_val) = schema.min {
             if n < min_val {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.min.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("Number must be at least {}", min_val));
-                errors.push(ValidationError::new(
-                    path.to_vec(),
-                    "number.min",
-                    msg,
-                ));
+                errors.push(
+                    error(messages.and_then(|m| m.min.as_ref()), "number.min")
+                        .with_param("minimum", min_val)
+                        .interpolate(label, Some(value)),
+                );
             }
         }
 
-        if let Some(max_val) = max {
+        if let Some(max_val) = schema.max {
             if n > max_val {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.max.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("Number must be at most {}", max_val));
-                errors.push(ValidationError::new(
-                    path.to_vec(),
-                    "number.max",
-                    msg,
-                ));
-            }
-        }
-
-        if integer == Some(true) && n.fract() != 0.0 {
-            let msg = messages
-                .as_ref()
-                .and_then(|m| m.integer.as_deref())
-                .unwrap_or("Number must be an integer");
-            errors.push(ValidationError::new(
-                path.to_vec(),
-                "number.integer",
-                msg,
-            ));
-        }
-
-        if positive == Some(true) && n <= 0.0 {
-            let msg = messages
-                .as_ref()
-                .and_then(|m| m.positive.as_deref())
-                .unwrap_or("Number must be positive");
-            errors.push(ValidationError::new(
-                path.to_vec(),
-                "number.positive",
-                msg,
-            ));
-        }
-    }
-
-    /// Validate object shape
-    fn validate_object(
-        shape: &HashMap<String, Schema>,
-        obj: &serde_json::Map<String, Value>,
-        messages: &Option<crate::schema::ObjectMessages>,
-        path: &[String],
-        errors: &mut Vec<ValidationError>,
-    ) {
-        for (key, field_schema) in shape {
-            let field_path: Vec<String> = path.iter().cloned().chain(std::iter::once(key.clone())).collect();
-            
-            if let Some(field_value) = obj.get(key) {
-                if let Err(field_errors) = Self::validate_with_path(field_schema, field_value, &field_path) {
-                    errors.extend(field_errors);
-                }
-            } else {
-                let msg = messages
-                    .as_ref()
-                    .and_then(|m| m.required.as_deref())
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| format!("Field '{}' is required", key));
-                errors.push(ValidationError::new(
-                    field_path,
-                    "required",
-                    msg,
-                ));
+                errors.push(
+                    error(messages.and_then(|m| m.max.as_ref()), "number.max")
+                        .with_param("maximum", max_val)
+                        .interpolate(label, Some(value)),
+                );
             }
         }
-    }
 
-    /// Navigate to a schema at a given path
-    fn navigate_schema<'a>(
-        schema: &'a Schema,
-        path: &[String],
-    ) -> Result<&'a Schema, Vec<ValidationError>> {
-        let mut current = schema;
+        if let Some(min_val) = schema.exclusive_min {
+            if n <= min_val {
+                errors.push(
+                    error(messages.and_then(|m| m.exclusive_min.as_ref()), "number.exclusive_min")
+                        .with_param("minimum", min_val)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
 
-        for segment in path {
-            match current {
-                Schema::Object { shape, .. } => {
-                    current = shape.get(segment).ok_or_else(|| {
-                        vec![ValidationError::new(
-                            vec![segment.clone()],
-                            "invalid_path",
-                            format!("Path segment '{}' not found in schema", segment),
-                        )]
-                    })?;
-                }
-                _ => {
-                    return Err(vec![ValidationError::new(
-                        vec![segment.clone()],
-                        "invalid_path",
-                        "Cannot navigate non-object schema",
-                    )]);
-                }
+        if let Some(max_val) = schema.exclusive_max {
+            if n >= max_val {
+                errors.push(
+                    error(messages.and_then(|m| m.exclusive_max.as_ref()), "number.exclusive_max")
+                        .with_param("maximum", max_val)
+                        .interpolate(label, Some(value)),
+                );
             }
         }
 
-        Ok(current)
-    }
+        if let Some(step) = schema.multiple_of {
+            if !Self::is_multiple_of(n, step) {
+                errors.push(
+                    error(messages.and_then(|m| m.multiple_of.as_ref()), "number.multiple_of")
+                        .with_param("multiple_of", step)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
 
-    /// Navigate to a value at a given path
-    fn navigate_value<'a>(value: &'a Value, path: &[String]) -> &'a Value {
-        let mut current = value;
+        if schema.integer == Some(true) && n.fract() != 0.0 {
+            errors.push(
+                error(messages.and_then(|m| m.integer.as_ref()), "number.integer")
+                    .interpolate(label, Some(value)),
+            );
+        }
 
-        for segment in path {
-            if let Some(obj) = current.as_object() {
-                current = obj.get(segment).unwrap_or(&Value::Null);
-            } else {
-                return &Value::Null;
+        let signs = [
+            (schema.positive, n > 0.0, messages.and_then(|m| m.positive.as_ref()), "number.positive"),
+            (schema.negative, n < 0.0, messages.and_then(|m| m.negative.as_ref()), "number.negative"),
+            (
+                schema.non_negative,
+                n >= 0.0,
+                messages.and_then(|m| m.non_negative.as_ref()),
+                "number.non_negative",
+            ),
+            (
+                schema.non_positive,
+                n <= 0.0,
+                messages.and_then(|m| m.non_positive.as_ref()),
+                "number.non_positive",
+            ),
+        ];
+        for (enabled, ok, custom, code) in signs {
+            if enabled == Some(true) && !ok {
+                errors.push(error(custom, code).interpolate(label, Some(value)));
             }
         }
 
-        current
-    }
+        if schema.finite == Some(true) && !n.is_finite() {
+            errors.push(
+                error(messages.and_then(|m| m.finite.as_ref()), "number.finite")
+                    .interpolate(label, Some(value)),
+            );
+        }
 
-    /// Simple email validation (RFC 5322 compliant in production)
-    fn is_valid_email(s: &str) -> bool {
-        s.contains('@') && s.split('@').count() == 2 && !s.starts_with('@') && !s.ends_with('@')
+        if schema.safe_integer == Some(true) && !(n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER) {
+            errors.push(
+                error(messages.and_then(|m| m.safe_integer.as_ref()), "number.safe_integer")
+                    .with_param("minimum", -MAX_SAFE_INTEGER)
+                    .with_param("maximum", MAX_SAFE_INTEGER)
+                    .interpolate(label, Some(value)),
+            );
+        }
     }
 
-    /// Simple URL validation
-    fn is_valid_url(s: &str) -> bool {
-        s.starts_with("http://") || s.starts_with("https://")
-    }
-}
+    /// Validate decimal constraints on the exact digits of the input
+    ///
+    /// Bounds and the actual value are reported as strings so no digits are lost.
+    fn validate_decimal(
+        ctx: &Context,
+        n: &Numeric,
+        value: &Value,
+        schema: &DecimalSchema,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let messages = schema.messages.as_ref();
+        let label = Self::label(&schema.ui);
+        let error = |custom: Option<&String>, code: &str| {
+            let msg = ctx.message(custom.map(String::as_str), code);
+            ValidationError::new(path.to_vec(), code, msg)
+        };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
+        if let Some(precision) = schema.precision {
+            // Like SQL DECIMAL(p, s), a scale also reserves digits for the fraction
+            let integer_digits = u64::from(precision.saturating_sub(schema.scale.unwrap_or(0)));
+            if n.precision() > u64::from(precision) || n.integer_digits() > integer_digits {
+                errors.push(
+                    error(messages.and_then(|m| m.precision.as_ref()), "decimal.precision")
+                        .with_param("precision", precision)
+                        .with_param("actual", n.precision())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(scale) = schema.scale {
+            if n.scale() > u64::from(scale) {
+                errors.push(
+                    error(messages.and_then(|m| m.scale.as_ref()), "decimal.scale")
+                        .with_param("scale", scale)
+                        .with_param("actual", n.scale())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(min) = &schema.min {
+            if n < min {
+                errors.push(
+                    error(messages.and_then(|m| m.min.as_ref()), "decimal.min")
+                        .with_param("minimum", min.to_string())
+                        .with_param("actual", n.to_string())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(max) = &schema.max {
+            if n > max {
+                errors.push(
+                    error(messages.and_then(|m| m.max.as_ref()), "decimal.max")
+                        .with_param("maximum", max.to_string())
+                        .with_param("actual", n.to_string())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+    }
+
+    /// Read an RFC 3339 date-time as an instant, applying the offset policy
+    fn read_datetime(
+        s: &str,
+        schema: &DateTimeSchema,
+        offset: UtcOffset,
+    ) -> Result<DatePoint, &'static str> {
+        let (local, own) = date::parse_datetime(s).ok_or("date.invalid")?;
+        let (_, _, zone) = format::split_datetime(s).ok_or("date.invalid")?;
+        if !format::offset_allowed(zone, schema.offset.unwrap_or_default()) {
+            return Err("date.offset");
+        }
+        let offset = own.map_or(offset, UtcOffset::from_minutes);
+        Ok(DatePoint::Instant(offset.to_instant(local)))
+    }
+
+    /// Report unreadable input or check a date against its bounds and ages
+    fn check_date(
+        ctx: &Context,
+        point: Result<DatePoint, &'static str>,
+        rules: &DateRules,
+        schema: &Schema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let messages = rules.messages;
+        let label = schema.label();
+        let point = match point {
+            Ok(point) => point,
+            Err(code) => {
+                let custom = messages.and_then(|m| match code {
+                    "invalid_type" => m.invalid_type.as_deref(),
+                    "date.offset" => m.offset.as_deref(),
+                    _ => m.invalid.as_deref(),
+                });
+                errors.push(if code == "invalid_type" {
+                    Self::invalid_type(ctx, path, schema.type_name(), value, custom, label)
+                } else {
+                    ValidationError::new(path.to_vec(), code, ctx.message(custom, code))
+                        .interpolate(label, Some(value))
+                });
+                return;
+            }
+        };
+
+        let now = ctx.now();
+        // Bounds resolve to the input's precision and are reported in it
+        // A shifted `now` beyond the calendar cannot be reached by any input
+        let compare = |bound: &DateBound| match point {
+            DatePoint::Date(days) => bound
+                .resolve_date(now, rules.offset)
+                .map(|bound| (days.cmp(&bound), date::format_date(bound))),
+            DatePoint::Instant(instant) => bound
+                .resolve_instant(now, rules.offset)
+                .map(|bound| (instant.cmp(&bound), date::format_instant(bound))),
+        };
+
+        if let Some((ordering, minimum)) = rules.min.and_then(compare) {
+            if ordering == Ordering::Less {
+                let msg = ctx.message(messages.and_then(|m| m.min.as_deref()), "date.min");
+                errors.push(
+                    ValidationError::new(path.to_vec(), "date.min", msg)
+                        .with_param("minimum", minimum)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some((ordering, maximum)) = rules.max.and_then(compare) {
+            if ordering == Ordering::Greater {
+                let msg = ctx.message(messages.and_then(|m| m.max.as_deref()), "date.max");
+                errors.push(
+                    ValidationError::new(path.to_vec(), "date.max", msg)
+                        .with_param("maximum", maximum)
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if rules.min_age.is_some() || rules.max_age.is_some() {
+            let birth = match point {
+                DatePoint::Date(days) => days,
+                DatePoint::Instant(instant) => rules.offset.date_of(instant),
+            };
+            let age = date::age(birth, rules.offset.date_of(now));
+
+            if let Some(min_age) = rules.min_age {
+                if age < i64::from(min_age) {
+                    let msg = ctx.message(messages.and_then(|m| m.min_age.as_deref()), "date.min_age");
+                    errors.push(
+                        ValidationError::new(path.to_vec(), "date.min_age", msg)
+                            .with_param("minimum", min_age)
+                            .with_param("actual", age)
+                            .interpolate(label, Some(value)),
+                    );
+                }
+            }
+
+            if let Some(max_age) = rules.max_age {
+                if age > i64::from(max_age) {
+                    let msg = ctx.message(messages.and_then(|m| m.max_age.as_deref()), "date.max_age");
+                    errors.push(
+                        ValidationError::new(path.to_vec(), "date.max_age", msg)
+                            .with_param("maximum", max_age)
+                            .with_param("actual", age)
+                            .interpolate(label, Some(value)),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Validate big integer bounds on the exact digits of the input
+    fn validate_bigint(
+        ctx: &Context,
+        n: &Numeric,
+        value: &Value,
+        schema: &BigIntSchema,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let messages = schema.messages.as_ref();
+        let label = Self::label(&schema.ui);
+
+        if let Some(min) = &schema.min {
+            if n < min {
+                let msg = ctx.message(messages.and_then(|m| m.min.as_deref()), "bigint.min");
+                errors.push(
+                    ValidationError::new(path.to_vec(), "bigint.min", msg)
+                        .with_param("minimum", min.to_string())
+                        .with_param("actual", n.to_string())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(max) = &schema.max {
+            if n > max {
+                let msg = ctx.message(messages.and_then(|m| m.max.as_deref()), "bigint.max");
+                errors.push(
+                    ValidationError::new(path.to_vec(), "bigint.max", msg)
+                        .with_param("maximum", max.to_string())
+                        .with_param("actual", n.to_string())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+    }
+
+    /// Whether `n` is a whole number of `step`s
+    ///
+    /// The quotient is compared with a tolerance of a few ulps so decimal
+    /// steps like `0.01` accept values such as `19.99` despite float rounding.
+    /// A step that is not a positive finite number accepts everything.
+    fn is_multiple_of(n: f64, step: f64) -> bool {
+        if !(step.is_finite() && step > 0.0) {
+            return true;
+        }
+        let quotient = n / step;
+        if !quotient.is_finite() {
+            return false;
+        }
+        (quotient - quotient.round()).abs() <= 8.0 * f64::EPSILON * quotient.abs().max(1.0)
+    }
+
+    /// Validate object shape and build its output
+    ///
+    /// Keys missing from the shape are handled by the `unknown_keys` policy;
+    /// by default they are left out of the output.
+    fn validate_object(
+        ctx: &Context,
+        schema: &ObjectSchema,
+        obj: &serde_json::Map<String, Value>,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> serde_json::Map<String, Value> {
+        let mut output = serde_json::Map::new();
+
+        for (key, field_schema) in &schema.shape {
+            let field_path = Self::child_path(path, key.clone());
+            let modifiers = field_schema.modifiers();
+
+            let field_value = obj.get(key).filter(|v| !ctx.is_blank(field_schema, v));
+
+            if let Some(field_value) = field_value {
+                let field_output = Self::parse_with_path(ctx, field_schema, field_value, &field_path, errors);
+                output.insert(key.clone(), field_output);
+            } else if let Some(default) = &modifiers.default {
+                let field_output = Self::parse_with_path(ctx, field_schema, default, &field_path, errors);
+                output.insert(key.clone(), field_output);
+            } else if !modifiers.is_optional() {
+                errors.push(Self::required_error(ctx, schema, key, field_schema, field_path));
+            }
+        }
+
+        let unknown: Vec<&str> = obj
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !schema.shape.contains_key(*key))
+            .collect();
+        if unknown.is_empty() {
+            return output;
+        }
+
+        match schema.unknown_keys.as_ref().unwrap_or(&UnknownKeys::Strip) {
+            UnknownKeys::Strip => {}
+            UnknownKeys::Strict => {
+                let msg = ctx.message(
+                    schema.messages.as_ref().and_then(|m| m.unrecognized_keys.as_deref()),
+                    "unrecognized_keys",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "unrecognized_keys", msg)
+                        .with_param("keys", unknown.clone())
+                        .interpolate(Self::label(&schema.ui), None),
+                );
+            }
+            UnknownKeys::Passthrough => {
+                for key in unknown {
+                    output.insert(key.to_string(), obj[key].clone());
+                }
+            }
+            UnknownKeys::Catchall(catchall) => {
+                for key in unknown {
+                    let field_path = Self::child_path(path, key.to_string());
+                    let field_output = Self::parse_with_path(ctx, catchall, &obj[key], &field_path, errors);
+                    output.insert(key.to_string(), field_output);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Validate array length, uniqueness and each element
+    fn validate_array(
+        ctx: &Context,
+        schema: &ArraySchema,
+        arr: &[Value],
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Vec<Value> {
+        let messages = &schema.messages;
+        let label = Self::label(&schema.ui);
+
+        if let Some(min_len) = schema.min {
+            if arr.len() < min_len {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.min.as_deref()),
+                    "array.min",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "array.min", msg)
+                        .with_param("minimum", min_len)
+                        .with_param("actual", arr.len())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(max_len) = schema.max {
+            if arr.len() > max_len {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.max.as_deref()),
+                    "array.max",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "array.max", msg)
+                        .with_param("maximum", max_len)
+                        .with_param("actual", arr.len())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if schema.unique == Some(true) {
+            let mut seen = HashMap::new();
+            for (index, item) in arr.iter().enumerate() {
+                let key = Self::canonical_key(item);
+                if let Some(&first_index) = seen.get(&key) {
+                    let msg = ctx.message(
+                        messages.as_ref().and_then(|m| m.unique.as_deref()),
+                        "array.unique",
+                    );
+                    errors.push(
+                        ValidationError::new(Self::child_path(path, index.to_string()), "array.unique", msg)
+                            .with_param("duplicate_of", first_index)
+                            .interpolate(label, Some(item)),
+                    );
+                } else {
+                    seen.insert(key, index);
+                }
+            }
+        }
+
+        arr.iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let item_path = Self::child_path(path, index.to_string());
+                Self::parse_with_path(ctx, &schema.items, item, &item_path, errors)
+            })
+            .collect()
+    }
+
+    /// Validate tuple positions and build its output
+    ///
+    /// Missing trailing positions take their default; elements past `items`
+    /// are validated against `rest` when it is set.
+    fn validate_tuple(
+        ctx: &Context,
+        schema: &TupleSchema,
+        arr: &[Value],
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Vec<Value> {
+        let messages = &schema.messages;
+        let label = Self::label(&schema.ui);
+        let required = schema
+            .items
+            .iter()
+            .rposition(|item| {
+                let modifiers = item.modifiers();
+                !modifiers.is_optional() && modifiers.default.is_none()
+            })
+            .map_or(0, |last| last + 1);
+
+        if arr.len() < required {
+            let msg = ctx.message(
+                messages.as_ref().and_then(|m| m.too_small.as_deref()),
+                "tuple.too_small",
+            );
+            errors.push(
+                ValidationError::new(path.to_vec(), "tuple.too_small", msg)
+                    .with_param("minimum", required)
+                    .with_param("actual", arr.len())
+                    .interpolate(label, Some(value)),
+            );
+        }
+
+        if schema.rest.is_none() && arr.len() > schema.items.len() {
+            let msg = ctx.message(
+                messages.as_ref().and_then(|m| m.too_big.as_deref()),
+                "tuple.too_big",
+            );
+            errors.push(
+                ValidationError::new(path.to_vec(), "tuple.too_big", msg)
+                    .with_param("maximum", schema.items.len())
+                    .with_param("actual", arr.len())
+                    .interpolate(label, Some(value)),
+            );
+        }
+
+        let mut output = Vec::with_capacity(arr.len().max(schema.items.len()));
+        for (index, item_schema) in schema.items.iter().enumerate() {
+            let item_path = Self::child_path(path, index.to_string());
+            // Output cannot skip a position, so filling stops at the first gap
+            let Some(item) = arr.get(index).or(item_schema.modifiers().default.as_ref()) else {
+                break;
+            };
+            output.push(Self::parse_with_path(ctx, item_schema, item, &item_path, errors));
+        }
+
+        if let Some(rest) = &schema.rest {
+            for (index, item) in arr.iter().enumerate().skip(schema.items.len()) {
+                let item_path = Self::child_path(path, index.to_string());
+                output.push(Self::parse_with_path(ctx, rest, item, &item_path, errors));
+            }
+        }
+
+        output
+    }
+
+    /// Validate record entries and build its output
+    ///
+    /// Keys take the output of the key schema, so key transforms apply. A key
+    /// the key schema rejects is reported once as `record.invalid_key`, and an
+    /// entry whose key collides with an earlier one is reported as
+    /// `record.duplicate_key` and dropped without validating its value.
+    fn validate_record(
+        ctx: &Context,
+        schema: &RecordSchema,
+        obj: &serde_json::Map<String, Value>,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> serde_json::Map<String, Value> {
+        let messages = &schema.messages;
+        let label = Self::label(&schema.ui);
+
+        if let Some(min_entries) = schema.min_entries {
+            if obj.len() < min_entries {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.min_entries.as_deref()),
+                    "record.min_entries",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "record.min_entries", msg)
+                        .with_param("minimum", min_entries)
+                        .with_param("actual", obj.len())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        if let Some(max_entries) = schema.max_entries {
+            if obj.len() > max_entries {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.max_entries.as_deref()),
+                    "record.max_entries",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "record.max_entries", msg)
+                        .with_param("maximum", max_entries)
+                        .with_param("actual", obj.len())
+                        .interpolate(label, Some(value)),
+                );
+            }
+        }
+
+        let mut output = serde_json::Map::new();
+        for (key, item) in obj {
+            let entry_path = Self::child_path(path, key.clone());
+            let mut output_key = key.clone();
+            let key_value = Value::from(key.as_str());
+
+            if let Some(key_schema) = &schema.keys {
+                let mut key_errors = Vec::new();
+                let parsed =
+                    Self::parse_with_path(ctx, key_schema, &key_value, &entry_path, &mut key_errors);
+                if let Some(first) = key_errors.first() {
+                    let msg = ctx.message(
+                        messages.as_ref().and_then(|m| m.invalid_key.as_deref()),
+                        "record.invalid_key",
+                    );
+                    errors.push(
+                        ValidationError::new(entry_path.clone(), "record.invalid_key", msg)
+                            .with_param("key", key.as_str())
+                            .with_param("reason", first.code.as_str())
+                            .interpolate(label, Some(&key_value)),
+                    );
+                } else if let Value::String(parsed) = parsed {
+                    output_key = parsed;
+                }
+            }
+
+            // Distinct input keys can collide once the key schema transforms them;
+            // the first entry is kept
+            if output.contains_key(&output_key) {
+                let msg = ctx.message(
+                    messages.as_ref().and_then(|m| m.duplicate_key.as_deref()),
+                    "record.duplicate_key",
+                );
+                errors.push(
+                    ValidationError::new(entry_path.clone(), "record.duplicate_key", msg)
+                        .with_param("key", key.as_str())
+                        .with_param("output_key", output_key.as_str())
+                        .interpolate(label, Some(&key_value)),
+                );
+                continue;
+            }
+
+            let parsed = Self::parse_with_path(ctx, &schema.values, item, &entry_path, errors);
+            output.insert(output_key, parsed);
+        }
+
+        output
+    }
+
+    /// Validate against each option, keeping the first match or the closest failure
+    fn validate_union(
+        ctx: &Context,
+        schema: &UnionSchema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Value {
+        let mut closest: Option<Vec<ValidationError>> = None;
+
+        for option in &schema.options {
+            let mut option_errors = Vec::new();
+            let output = Self::parse_with_path(ctx, option, value, path, &mut option_errors);
+            if option_errors.is_empty() {
+                return output;
+            }
+
+            let score = Self::mismatch_score(&option_errors, path);
+            if closest.as_ref().is_none_or(|best| score < Self::mismatch_score(best, path)) {
+                closest = Some(option_errors);
+            }
+        }
+
+        let msg = ctx.message(
+            schema.messages.as_ref().and_then(|m| m.invalid_union.as_deref()),
+            "invalid_union",
+        );
+        errors.push(
+            ValidationError::new(path.to_vec(), "invalid_union", msg)
+                .with_param("received", Self::type_name(value))
+                .interpolate(Self::label(&schema.ui), Some(value)),
+        );
+        errors.extend(closest.unwrap_or_default());
+
+        value.clone()
+    }
+
+    /// Validate against every option and merge their outputs
+    ///
+    /// Outputs that disagree, e.g. because one option transforms a field the
+    /// other passes through, are reported as `invalid_intersection`.
+    fn validate_intersection(
+        ctx: &Context,
+        schema: &IntersectionSchema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Value {
+        let before = errors.len();
+        let outputs: Vec<Value> = schema
+            .options
+            .iter()
+            .map(|option| Self::parse_with_path(ctx, option, value, path, errors))
+            .collect();
+        if errors.len() > before {
+            return value.clone();
+        }
+
+        let mut outputs = outputs.into_iter();
+        let first = outputs.next().unwrap_or_else(|| value.clone());
+        match outputs.try_fold(first, Self::merge_outputs) {
+            Some(merged) => merged,
+            None => {
+                let msg = ctx.message(
+                    schema.messages.as_ref().and_then(|m| m.invalid_intersection.as_deref()),
+                    "invalid_intersection",
+                );
+                errors.push(
+                    ValidationError::new(path.to_vec(), "invalid_intersection", msg)
+                        .interpolate(Self::label(&schema.ui), Some(value)),
+                );
+                value.clone()
+            }
+        }
+    }
+
+    /// Merge two outputs of the same value: object keys are combined, arrays
+    /// merged element-wise, and anything else must be equal
+    fn merge_outputs(a: Value, b: Value) -> Option<Value> {
+        match (a, b) {
+            (Value::Object(mut a), Value::Object(b)) => {
+                for (key, b_value) in b {
+                    match a.get_mut(&key) {
+                        Some(a_value) => *a_value = Self::merge_outputs(a_value.take(), b_value)?,
+                        None => {
+                            a.insert(key, b_value);
+                        }
+                    }
+                }
+                Some(Value::Object(a))
+            }
+            (Value::Array(a), Value::Array(b)) if a.len() == b.len() => a
+                .into_iter()
+                .zip(b)
+                .map(|(a, b)| Self::merge_outputs(a, b))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            (a, b) => Self::values_equal(&a, &b).then_some(a),
+        }
+    }
+
+    /// Rank a failed union option: options whose type matched beat those that
+    /// did not, then fewer errors beat more
+    fn mismatch_score(errors: &[ValidationError], path: &[String]) -> (bool, usize) {
+        let type_mismatch = errors.iter().any(|e| {
+            e.path == path
+                && matches!(
+                    e.code.as_str(),
+                    "invalid_type" | "invalid_literal" | "invalid_enum_value" | "invalid_union"
+                )
+        });
+        (type_mismatch, errors.len())
+    }
+
+    /// Validate against the single option selected by the discriminator field
+    fn validate_discriminated_union(
+        ctx: &Context,
+        schema: &DiscriminatedUnionSchema,
+        value: &Value,
+        path: &[String],
+        errors: &mut Vec<ValidationError>,
+    ) -> Value {
+        if let Some(option) = ctx.discriminated_option(schema, value) {
+            return Self::parse_with_path(ctx, option, value, path, errors);
+        }
+
+        let tags: Vec<Value> = schema.options
+            .iter()
+            .flat_map(|option| Self::discriminator_values(ctx.definitions, option, &schema.discriminator))
+            .cloned()
+            .collect();
+        let tag = value.get(&schema.discriminator).cloned().unwrap_or(Value::Null);
+        let msg = ctx.message(
+            schema.messages.as_ref().and_then(|m| m.invalid_union_discriminator.as_deref()),
+            "invalid_union_discriminator",
+        );
+        errors.push(
+            ValidationError::new(Self::child_path(path, schema.discriminator.clone()), "invalid_union_discriminator", msg)
+                .with_param("options", tags)
+                .with_param("received", tag.clone())
+                .interpolate(Self::label(&schema.ui), Some(&tag)),
+        );
+
+        value.clone()
+    }
+
+    /// Find the option whose discriminator accepts the value's tag
+    pub(crate) fn discriminated_option<'a>(
+        definitions: &'a IndexMap<String, Schema>,
+        schema: &'a DiscriminatedUnionSchema,
+        value: &Value,
+    ) -> Option<&'a Schema> {
+        let tag = value.as_object()?.get(&schema.discriminator)?;
+        schema.options.iter().find(|option| {
+            Self::discriminator_values(definitions, option, &schema.discriminator)
+                .any(|allowed| Self::values_equal(allowed, tag))
+        })
+    }
+
+    /// Tag values an option accepts for the discriminator field
+    ///
+    /// The option and the field may both be references.
+    pub(crate) fn discriminator_values<'a>(
+        definitions: &'a IndexMap<String, Schema>,
+        option: &'a Schema,
+        discriminator: &str,
+    ) -> impl Iterator<Item = &'a Value> {
+        let field = match option.resolve(definitions) {
+            Some(Schema::Object(object)) => object.shape.get(discriminator),
+            _ => None,
+        };
+        let field = field.and_then(|field| field.resolve(definitions));
+        let values: &[Value] = match field {
+            Some(Schema::Literal(literal)) => std::slice::from_ref(&literal.value),
+            Some(Schema::Enum(enumeration)) => &enumeration.values,
+            _ => &[],
+        };
+        values.iter()
+    }
+
+    /// Build the path of a nested field or array element
+    fn child_path(path: &[String], segment: String) -> Vec<String> {
+        path.iter().cloned().chain(std::iter::once(segment)).collect()
+    }
+
+    /// Navigate to a schema at a given path
+    ///
+    /// The value is walked alongside so unions can pick the branch it belongs to.
+    /// References are followed as the path descends; each segment consumes one
+    /// level, so recursive definitions cannot loop.
+    fn navigate_schema<'a>(
+        definitions: &'a IndexMap<String, Schema>,
+        schema: &'a Schema,
+        value: &Value,
+        path: &[String],
+    ) -> Result<&'a Schema, Vec<ValidationError>> {
+        let mut current = schema;
+        let mut current_value = Some(value);
+
+        for segment in path {
+            match Self::select_branch(definitions, current, current_value, segment, definitions.len()) {
+                Schema::Object(object) => {
+                    current = object.shape.get(segment).or_else(|| object.catchall()).ok_or_else(|| {
+                        vec![ValidationError::new(
+                            vec![segment.clone()],
+                            "invalid_path",
+                            format!("Path segment '{}' not found in schema", segment),
+                        )]
+                    })?;
+                }
+                Schema::Record(record) => current = &record.values,
+                Schema::Tuple(tuple) => {
+                    let item = segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| tuple.items.get(index).or(tuple.rest.as_deref()));
+                    current = item.ok_or_else(|| {
+                        vec![ValidationError::new(
+                            vec![segment.clone()],
+                            "invalid_path",
+                            format!("Path segment '{}' is not a tuple position", segment),
+                        )]
+                    })?;
+                }
+                Schema::Array(array) => {
+                    if segment.parse::<usize>().is_err() {
+                        return Err(vec![ValidationError::new(
+                            vec![segment.clone()],
+                            "invalid_path",
+                            format!("Path segment '{}' is not an array index", segment),
+                        )]);
+                    }
+                    current = &array.items;
+                }
+                _ => {
+                    return Err(vec![ValidationError::new(
+                        vec![segment.clone()],
+                        "invalid_path",
+                        "Cannot navigate non-object schema",
+                    )]);
+                }
+            }
+            current_value = current_value.and_then(|v| Self::navigate_value(v, std::slice::from_ref(segment)));
+        }
+
+        Ok(current)
+    }
+
+    /// Resolve references and unions to the schema a path segment descends into
+    ///
+    /// `budget` is the number of references still allowed to expand; a longer
+    /// chain revisits a definition without descending, so it is cut off.
+    fn select_branch<'a>(
+        definitions: &'a IndexMap<String, Schema>,
+        schema: &'a Schema,
+        value: Option<&Value>,
+        segment: &str,
+        budget: usize,
+    ) -> &'a Schema {
+        let has_segment = |o: &&Schema| Self::has_segment(definitions, o, segment, budget);
+        let (selected, budget) = match schema {
+            Schema::Ref(reference) if budget > 0 => {
+                (definitions.get(&reference.name), budget - 1)
+            }
+            Schema::DiscriminatedUnion(union) => (
+                value
+                    .and_then(|v| Self::discriminated_option(definitions, union, v))
+                    .or_else(|| union.options.iter().find(has_segment)),
+                budget,
+            ),
+            Schema::Union(union) => (union.options.iter().find(has_segment), budget),
+            Schema::Intersection(intersection) => {
+                (intersection.options.iter().find(has_segment), budget)
+            }
+            _ => (None, budget),
+        };
+
+        match selected {
+            Some(option) => Self::select_branch(definitions, option, value, segment, budget),
+            None => schema,
+        }
+    }
+
+    /// Whether a path segment can descend into a schema
+    fn has_segment(
+        definitions: &IndexMap<String, Schema>,
+        schema: &Schema,
+        segment: &str,
+        budget: usize,
+    ) -> bool {
+        match schema {
+            Schema::Object(object) => object.shape.contains_key(segment) || object.catchall().is_some(),
+            Schema::Array(_) => segment.parse::<usize>().is_ok(),
+            Schema::Record(_) => true,
+            Schema::Tuple(tuple) => segment
+                .parse::<usize>()
+                .is_ok_and(|index| index < tuple.items.len() || tuple.rest.is_some()),
+            Schema::Union(union) => union
+                .options
+                .iter()
+                .any(|o| Self::has_segment(definitions, o, segment, budget)),
+            Schema::DiscriminatedUnion(union) => union
+                .options
+                .iter()
+                .any(|o| Self::has_segment(definitions, o, segment, budget)),
+            Schema::Intersection(intersection) => intersection
+                .options
+                .iter()
+                .any(|o| Self::has_segment(definitions, o, segment, budget)),
+            Schema::Ref(reference) => budget > 0
+                && definitions
+                    .get(&reference.name)
+                    .is_some_and(|target| Self::has_segment(definitions, target, segment, budget - 1)),
+            _ => false,
+        }
+    }
+
+    /// Navigate to a value at a given path, `None` if it is missing
+    fn navigate_value<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+        let mut current = value;
+
+        for segment in path {
+            current = match current {
+                Value::Object(obj) => obj.get(segment)?,
+                Value::Array(arr) => arr.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+
+        Some(current)
+    }
+
+    /// Serialized form of a value with object keys sorted and numbers
+    /// normalized, so objects that differ only in key order and numbers that
+    /// differ only in spelling compare equal
+    pub(crate) fn canonical_key(value: &Value) -> String {
+        match value {
+            Value::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                let fields: Vec<String> = entries
+                    .into_iter()
+                    .map(|(key, item)| format!("{}:{}", Value::from(key.as_str()), Self::canonical_key(item)))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            }
+            Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(Self::canonical_key).collect();
+                format!("[{}]", items.join(","))
+            }
+            Value::Number(_) => match Numeric::from_value(value) {
+                Some(n) => n.canonical(),
+                None => value.to_string(),
+            },
+            other => other.to_string(),
+        }
+    }
+
+    /// Compare JSON values, treating `1` and `1.0` as the same number
+    fn values_equal(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Number(_), Value::Number(_)) => Numeric::from_value(a) == Numeric::from_value(b),
+            _ => a == b,
+        }
+    }
+
+    /// A JSON number as `f64`, infinite when it is beyond the `f64` range
+    ///
+    /// `as_f64` gives up on such literals (e.g. `1e400`), which would turn a
+    /// range problem into a type error.
+    fn float_value(value: &Value) -> Option<f64> {
+        match value {
+            Value::Number(n) => n.as_f64().or_else(|| n.to_string().parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// JSON type name of a value, as reported in `received` params
+    fn type_name(value: &Value) -> &'static str {
+        match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Field label from a node's UI configuration
+    fn label(ui: &Option<UiConfig>) -> Option<&str> {
+        ui.as_ref().and_then(|ui| ui.label.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use indexmap::IndexMap;
+
+    /// Error codes of a validation result, empty when it passed
+    fn error_codes(result: ValidationResult) -> Vec<String> {
+        result.err().unwrap_or_default().into_iter().map(|e| e.code).collect()
+    }
+
+    #[test]
+    fn test_string_validation() {
+        let schema = Schema::String(StringSchema {
+            min: Some(3),
+            max: Some(10),
+            ..Default::default()
+        });
+
+        assert!(Validator::validate(&schema, &json!("hello")).is_ok());
+        assert!(Validator::validate(&schema, &json!("hi")).is_err());
+        assert!(Validator::validate(&schema, &json!("verylongstring")).is_err());
+    }
+
+    #[test]
+    fn test_email_validation() {
+        let schema = Schema::String(StringSchema {
+            email: Some(true),
+            ..Default::default()
+        });
+
+        assert!(Validator::validate(&schema, &json!("test@example.com")).is_ok());
+        assert!(Validator::validate(&schema, &json!("invalid")).is_err());
+    }
+
+    #[test]
+    fn test_number_validation() {
+        let schema = Schema::Number(NumberSchema {
+            min: Some(0.0),
+            max: Some(100.0),
+            integer: Some(true),
+            ..Default::default()
+        });
+
+        assert!(Validator::validate(&schema, &json!(50)).is_ok());
+        assert!(Validator::validate(&schema, &json!(-1)).is_err());
+        assert!(Validator::validate(&schema, &json!(50.5)).is_err());
+    }
+
+    #[test]
+    fn test_object_validation() {
+        let mut shape = IndexMap::new();
+        shape.insert(
+            "name".to_string(),
+            Schema::String(StringSchema {
+                min: Some(1),
+                ..Default::default()
+            }),
+        );
+        shape.insert(
+            "age".to_string(),
+            Schema::Number(NumberSchema {
+                min: Some(0.0),
+                integer: Some(true),
+                positive: Some(true),
+                ..Default::default()
+            }),
+        );
+
+        let schema = Schema::Object(ObjectSchema { shape, ..Default::default() });
+
+        assert!(Validator::validate(&schema, &json!({"name": "John", "age": 30})).is_ok());
+        assert!(Validator::validate(&schema, &json!({"name": "", "age": 30})).is_err());
+        assert!(Validator::validate(&schema, &json!({"name": "John"})).is_err());
+    }
+
+    #[test]
+    fn test_validate_at_path() {
+        let mut shape = IndexMap::new();
+        shape.insert(
+            "email".to_string(),
+            Schema::String(StringSchema {
+                min: Some(3),
+                email: Some(true),
+                ..Default::default()
+            }),
+        );
+
+        let schema = Schema::Object(ObjectSchema { shape, ..Default::default() });
+        let value = json!({"email": "test@example.com"});
+
+        assert!(Validator::validate_at_path(&schema, &value, &["email".to_string()]).is_ok());
+        
+        let invalid_value = json!({"email": "invalid"});
+        assert!(Validator::validate_at_path(&schema, &invalid_value, &["email".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_custom_string_messages() {
+        use crate::schema::StringMessages;
+        
+        let messages = StringMessages {
+            invalid_type: Some("Custom: not a string".to_string()),
+            min: Some("Custom: too short".to_string()),
+            max: Some("Custom: too long".to_string()),
+            email: Some("Custom: bad email".to_string()),
+            ..Default::default()
+        };
+
+        let schema = Schema::String(StringSchema {
+            min: Some(5),
+            max: Some(10),
+            email: Some(true),
+            messages: Some(messages),
+            ..Default::default()
+        });
+
+        // Test invalid type
+        let result = Validator::validate(&schema, &json!(123));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors[0].message, "Custom: not a string");
+
+        // Test min length
+        let result = Validator::validate(&schema, &json!("abc"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: too short"));
+
+        // Test max length
+        let result = Validator::validate(&schema, &json!("verylongstring"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: too long"));
+
+        // Test email
+        let result = Validator::validate(&schema, &json!("hello"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: bad email"));
+    }
+
+    #[test]
+    fn test_custom_number_messages() {
+        use crate::schema::NumberMessages;
+        
+        let messages = NumberMessages {
+            invalid_type: Some("Custom: not a number".to_string()),
+            min: Some("Custom: too small".to_string()),
+            max: Some("Custom: too large".to_string()),
+            integer: Some("Custom: must be whole number".to_string()),
+            positive: Some("Custom: must be positive".to_string()),
+            ..Default::default()
+        };
+
+        let schema = Schema::Number(NumberSchema {
+            min: Some(10.0),
+            max: Some(100.0),
+            integer: Some(true),
+            positive: Some(true),
+            messages: Some(messages),
+            ..Default::default()
+        });
+
+        // Test invalid type
+        let result = Validator::validate(&schema, &json!("not a number"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors[0].message, "Custom: not a number");
+
+        // Test min value
+        let result = Validator::validate(&schema, &json!(5));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: too small"));
+
+        // Test max value
+        let result = Validator::validate(&schema, &json!(150));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: too large"));
+
+        // Test integer
+        let result = Validator::validate(&schema, &json!(50.5));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: must be whole number"));
+
+        // Test positive
+        let result = Validator::validate(&schema, &json!(-10));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert!(errors.iter().any(|e| e.message == "Custom: must be positive"));
+    }
+
+    #[test]
+    fn test_custom_object_messages() {
+        use crate::schema::ObjectMessages;
+        
+        let messages = ObjectMessages {
+            invalid_type: Some("Custom: not an object".to_string()),
+            required: Some("Custom: field is required".to_string()),
+            ..Default::default()
+        };
+
+        let mut shape = IndexMap::new();
+        shape.insert(
+            "name".to_string(),
+            Schema::String(StringSchema::default()),
+        );
+
+        let schema = Schema::Object(ObjectSchema {
+            shape,
+            messages: Some(messages),
+            ..Default::default()
+        });
+
+        // Test invalid type
+        let result = Validator::validate(&schema, &json!("not an object"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors[0].message, "Custom: not an object");
+
+        // Test required field
+        let result = Validator::validate(&schema, &json!({}));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors[0].message, "Custom: field is required");
+    }
+
+    #[test]
+    fn test_default_messages_when_custom_not_provided() {
+        let schema = Schema::String(StringSchema {
+            min: Some(5),
+            ..Default::default()
+        });
+
+        let result = Validator::validate(&schema, &json!("abc"));
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors[0].message, "String must be at least 5 characters");
+    }
+
+    #[test]
+    fn test_array_validation() {
+        let mut item_shape = IndexMap::new();
+        item_shape.insert(
+            "price".to_string(),
+            Schema::Number(NumberSchema {
+                min: Some(0.0),
+                ..Default::default()
+            }),
+        );
+
+        let schema = Schema::Array(ArraySchema {
+            min: Some(1),
+            max: Some(3),
+            ..ArraySchema::new(Schema::Object(ObjectSchema {
+                shape: item_shape,
+                ..Default::default()
+            }))
+        });
+
+        assert!(Validator::validate(&schema, &json!([{"price": 1}, {"price": 2}])).is_ok());
+        assert!(Validator::validate(&schema, &json!([])).is_err());
+        assert!(Validator::validate(&schema, &json!({"price": 1})).is_err());
+
+        let result = Validator::validate(&schema, &json!([{"price": 1}, {"price": -5}]));
+        let errors = result.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["1", "price"]);
+        assert_eq!(errors[0].code, "number.min");
+
+        let result = Validator::validate(&schema, &json!([{"price": 1}, {"price": 2}, {"price": 3}, {"price": 4}]));
+        assert_eq!(result.unwrap_err()[0].code, "array.max");
+    }
+
+    #[test]
+    fn test_array_unique() {
+        let schema = Schema::Array(ArraySchema {
+            unique: Some(true),
+            ..ArraySchema::new(Schema::String(StringSchema::default()))
+        });
+
+        assert!(Validator::validate(&schema, &json!(["a", "b", "c"])).is_ok());
+
+        let errors = Validator::validate(&schema, &json!(["a", "b", "a"])).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "array.unique");
+        assert_eq!(errors[0].path, vec!["2"]);
+
+        // Objects are compared regardless of key order
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "array",
+            "unique": true,
+            "items": {"type": "object", "shape": {}, "unknown_keys": "passthrough"}
+        }))
+        .unwrap();
+        let value: Value = serde_json::from_str(r#"[{"a": 1, "b": 2}, {"b": 2, "a": 1}]"#).unwrap();
+        let errors = Validator::validate(&schema, &value).unwrap_err();
+        assert_eq!(errors[0].path, vec!["1"]);
+
+        // Numbers are compared by value, not by their JSON spelling
+        let schema = Schema::Array(ArraySchema {
+            unique: Some(true),
+            ..ArraySchema::new(Schema::Number(NumberSchema::default()))
+        });
+        for input in ["[1, 1.0]", "[0.1, 0.10]", "[100, 1e2]", "[0, -0.0]"] {
+            let value: Value = serde_json::from_str(input).unwrap();
+            let errors = Validator::validate(&schema, &value).unwrap_err();
+            assert_eq!(errors[0].code, "array.unique", "{}", input);
+            assert_eq!(errors[0].path, vec!["1"], "{}", input);
+        }
+        let value: Value = serde_json::from_str("[1, 10, 0.1, -1]").unwrap();
+        assert!(Validator::validate(&schema, &value).is_ok());
+    }
+
+    #[test]
+    fn test_tuple_schema() {
+        let point: Schema = serde_json::from_value(json!({
+            "type": "tuple",
+            "items": [
+                {"type": "number", "min": -90, "max": 90},
+                {"type": "number", "min": -180, "max": 180},
+                {"type": "string", "default": "WGS84"}
+            ]
+        }))
+        .unwrap();
+        let codes = |schema: &Schema, value: Value| error_codes(Validator::validate(schema, &value));
+
+        assert_eq!(Validator::parse(&point, &json!([52.5, 13.4])).unwrap(), json!([52.5, 13.4, "WGS84"]));
+        assert_eq!(codes(&point, json!([52.5])), ["tuple.too_small"]);
+        assert_eq!(codes(&point, json!([52.5, 13.4, "ETRS89", 1])), ["tuple.too_big"]);
+        assert_eq!(codes(&point, json!({"lat": 52.5})), ["invalid_type"]);
+
+        let errors = Validator::validate(&point, &json!([52.5, 200])).unwrap_err();
+        assert_eq!(errors[0].code, "number.max");
+        assert_eq!(errors[0].path, vec!["1"]);
+        assert_eq!(errors[0].message, "Number must be at most 180");
+
+        let row: Schema = serde_json::from_value(json!({
+            "type": "tuple",
+            "items": [{"type": "string"}],
+            "rest": {"type": "number"}
+        }))
+        .unwrap();
+        assert!(codes(&row, json!(["total", 1, 2, 3])).is_empty());
+        assert_eq!(codes(&row, json!([])), ["tuple.too_small"]);
+        let errors = Validator::validate(&row, &json!(["total", 1, "2"])).unwrap_err();
+        assert_eq!(errors[0].path, vec!["2"]);
+
+        let errors = Validator::validate_at_path(&row, &json!(["total", 1, "2"]), &["2".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_type");
+        let errors = Validator::validate_at_path(&point, &json!([1, 2]), &["3".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_path");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_record_schema() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "record",
+            "keys": {"type": "string", "pattern": "^[a-z]{2}(-[A-Z]{2})?$", "trim": true},
+            "values": {"type": "string", "min": 1},
+            "max_entries": 3
+        }))
+        .unwrap();
+
+        let output = Validator::parse(&schema, &json!({"en": "Hello", " de ": "Hallo"})).unwrap();
+        assert_eq!(output, json!({"en": "Hello", "de": "Hallo"}));
+
+        let errors = Validator::validate(&schema, &json!({"en": "", "EN_us": "Hi"})).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, "string.min");
+        assert_eq!(errors[0].path, vec!["en"]);
+        assert_eq!(errors[1].code, "record.invalid_key");
+        assert_eq!(errors[1].path, vec!["EN_us"]);
+        assert_eq!(errors[1].params["reason"], "string.pattern");
+        assert_eq!(errors[1].message, "Invalid key 'EN_us'");
+
+        let errors =
+            Validator::validate(&schema, &json!({"en": "a", "de": "b", "fr": "c", "ja": "d"})).unwrap_err();
+        assert_eq!(errors[0].code, "record.max_entries");
+        assert!(errors[0].path.is_empty());
+
+        // Keys that only differ before the key transform collide
+        let lower: Schema = serde_json::from_value(json!({
+            "type": "record",
+            "keys": {"type": "string", "to_lower_case": true},
+            "values": {"type": "number"}
+        }))
+        .unwrap();
+        for input in [r#"{"A": 1, "a": 2}"#, r#"{"a": 1, "A": 2}"#] {
+            let value: Value = serde_json::from_str(input).unwrap();
+            let errors = Validator::validate(&lower, &value).unwrap_err();
+            assert_eq!(errors.len(), 1, "{}", input);
+            assert_eq!(errors[0].code, "record.duplicate_key");
+            assert_eq!(errors[0].path, vec![value.as_object().unwrap().keys().nth(1).unwrap().clone()]);
+            assert_eq!(errors[0].params["output_key"], "a");
+        }
+        let value: Value = serde_json::from_str(r#"{"a": 1, "A": 2}"#).unwrap();
+        let error = Validator::validate(&lower, &value).unwrap_err().remove(0);
+        assert_eq!(error.params["key"], "A");
+        assert_eq!(error.message, "Duplicate key 'a'");
+        assert_eq!(Validator::parse(&lower, &json!({"B": 1, "c": 2})).unwrap(), json!({"b": 1, "c": 2}));
+
+        // A dropped duplicate's value is not validated, and `{value}` is the key
+        let custom: Schema = serde_json::from_value(json!({
+            "type": "record",
+            "keys": {"type": "string", "to_lower_case": true, "max": 2},
+            "values": {"type": "number"},
+            "messages": {"invalid_key": "Bad key {value}", "duplicate_key": "Repeated key {value}"}
+        }))
+        .unwrap();
+        let value: Value = serde_json::from_str(r#"{"a": 1, "A": "x", "abc": 2}"#).unwrap();
+        let errors = Validator::validate(&custom, &value).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Repeated key A");
+        assert_eq!(errors[1].message, "Bad key abc");
+
+        let flags: Schema = serde_json::from_value(json!({
+            "type": "record",
+            "keys": {"type": "enum", "values": ["beta", "dark_mode"]},
+            "values": {"type": "boolean"},
+            "min_entries": 1
+        }))
+        .unwrap();
+        assert!(Validator::validate(&flags, &json!({"beta": true})).is_ok());
+        assert_eq!(Validator::validate(&flags, &json!({})).unwrap_err()[0].code, "record.min_entries");
+        assert_eq!(Validator::validate(&flags, &json!({"gamma": true})).unwrap_err()[0].params["reason"], "invalid_enum_value");
+
+        let errors =
+            Validator::validate_at_path(&flags, &json!({"beta": "yes"}), &["beta".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_type");
+    }
+
+    #[test]
+    fn test_recursive_schema() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "ref",
+            "name": "Comment",
+            "definitions": {
+                "Comment": {
+                    "type": "object",
+                    "shape": {
+                        "body": {"type": "string", "min": 1},
+                        "replies": {"type": "array", "items": {"type": "ref", "name": "Comment"}, "default": []}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let thread = json!({
+            "body": "First",
+            "replies": [{"body": "Reply", "replies": [{"body": ""}]}]
+        });
+
+        let errors = Validator::validate(&schema, &thread).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "string.min");
+        assert_eq!(errors[0].path, vec!["replies", "0", "replies", "0", "body"]);
+
+        let output = Validator::parse(&schema, &json!({"body": "First", "replies": [{"body": "Reply"}]})).unwrap();
+        assert_eq!(output["replies"][0]["replies"], json!([]));
+
+        let errors = Validator::validate(&schema, &json!({"replies": []})).unwrap_err();
+        assert_eq!(errors[0].code, "required");
+        assert_eq!(errors[0].params["expected"], "string");
+
+        let path: Vec<String> = ["replies", "0", "replies", "0", "body"].map(String::from).to_vec();
+        let errors = Validator::validate_at_path(&schema, &thread, &path).unwrap_err();
+        assert_eq!(errors[0].code, "string.min");
+        assert_eq!(errors[0].path, path);
+
+        // A definition that only refers back to itself through a union ends navigation
+        let looping: Schema = serde_json::from_value(json!({
+            "type": "ref",
+            "name": "Loop",
+            "definitions": {"Loop": {"type": "union", "options": [{"type": "ref", "name": "Loop"}, {"type": "string"}]}}
+        }))
+        .unwrap();
+        let errors = Validator::validate_at_path(&looping, &json!("a"), &["x".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_path");
+
+        let unknown: Schema = serde_json::from_value(json!({"type": "ref", "name": "Missing"})).unwrap();
+        let errors = Validator::validate(&unknown, &json!(1)).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_schema");
+        assert_eq!(errors[0].params["reference"], "Missing");
+
+        // Definitions below the root would never be looked up, so they are rejected
+        let nested = |schema: Value| serde_json::from_value::<Schema>(schema).unwrap_err().to_string();
+        let error = nested(json!({
+            "type": "object",
+            "shape": {"tree": {"type": "ref", "name": "Tree", "definitions": {"Tree": {"type": "string"}}}}
+        }));
+        assert_eq!(error, "`definitions` are only allowed on the root schema");
+        nested(json!({
+            "type": "ref",
+            "name": "Tree",
+            "definitions": {"Tree": {"type": "array", "items": {"type": "string"}, "definitions": {"Leaf": {"type": "string"}}}}
+        }));
+        nested(json!({
+            "type": "union",
+            "options": [{"type": "array", "items": {"type": "string", "definitions": {"Leaf": {"type": "string"}}}}]
+        }));
+    }
+
+    #[test]
+    fn test_recursion_depth_limit() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "ref",
+            "name": "Category",
+            "definitions": {
+                "Category": {
+                    "type": "object",
+                    "shape": {
+                        "name": {"type": "string"},
+                        "children": {"type": "array", "items": {"type": "ref", "name": "Category"}, "optional": true}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let nested = |depth: usize| {
+            (0..depth).fold(json!({"name": "leaf"}), |child, _| json!({"name": "node", "children": [child]}))
+        };
+
+        assert!(Validator::validate(&schema, &nested(DEFAULT_MAX_DEPTH - 1)).is_ok());
+
+        let errors = Validator::validate(&schema, &nested(DEFAULT_MAX_DEPTH + 10)).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "too_deep");
+        assert_eq!(errors[0].path.len(), 2 * DEFAULT_MAX_DEPTH);
+        assert_eq!(errors[0].message, "Value is nested too deeply (at most 32 levels)");
+
+        let options = ValidationOptions { max_depth: 3, ..ValidationOptions::default() };
+        let errors = Validator::validate_with_options(&schema, &nested(3), &options).unwrap_err();
+        assert_eq!(errors[0].code, "too_deep");
+        assert!(Validator::validate_with_options(&schema, &nested(2), &options).is_ok());
+    }
+
+    #[test]
+    fn test_object_composition() {
+        let user: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "id": {"type": "string"},
+                "email": {"type": "string", "email": true},
+                "name": {"type": "string", "optional": true},
+                "address": {
+                    "type": "object",
+                    "shape": {"city": {"type": "string"}, "zip": {"type": "string"}}
+                }
+            }
+        }))
+        .unwrap();
+        let keys = |schema: &Schema| match schema {
+            Schema::Object(object) => object.shape.keys().cloned().collect::<Vec<_>>(),
+            _ => panic!("expected an object schema"),
+        };
+
+        let create = user.omit(&["id"]).unwrap();
+        assert_eq!(keys(&create), ["email", "name", "address"]);
+        assert_eq!(keys(&user.pick(&["name", "id"]).unwrap()), ["id", "name"]);
+
+        let response = user
+            .extend(IndexMap::from([("created_at".to_string(), Schema::DateTime(DateTimeSchema::default()))]))
+            .unwrap();
+        assert_eq!(keys(&response), ["id", "email", "name", "address", "created_at"]);
+
+        let strict: Schema =
+            serde_json::from_value(json!({"type": "object", "shape": {"id": {"type": "number"}}, "unknown_keys": "strict"}))
+                .unwrap();
+        let merged = user.merge(&strict).unwrap();
+        assert_eq!(keys(&merged), ["id", "email", "name", "address"]);
+        let errors = Validator::validate(&merged, &json!({"id": "1", "email": "a@b.co", "address": {"city": "Berlin", "zip": "10115"}, "x": 1})).unwrap_err();
+        let codes: Vec<&str> = errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, ["invalid_type", "unrecognized_keys"]);
+
+        let update = user.partial().unwrap();
+        assert!(Validator::validate(&update, &json!({})).is_ok());
+        let errors = Validator::validate(&update, &json!({"address": {}})).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(Validator::validate(&user.deep_partial(), &json!({"address": {}})).is_ok());
+
+        let errors = Validator::validate(&update.required().unwrap(), &json!({})).unwrap_err();
+        assert_eq!(errors.len(), 4);
+
+        let error = Schema::String(StringSchema::default()).partial().unwrap_err();
+        assert_eq!(error.to_string(), "'partial' requires an object schema, found string");
+
+        // Merging combines definitions and rejects a name defined two ways
+        let with_definitions = |definitions: Value| -> Schema {
+            serde_json::from_value(json!({"type": "object", "shape": {}, "definitions": definitions})).unwrap()
+        };
+        let tag = with_definitions(json!({"Tag": {"type": "string"}}));
+        let merged = tag
+            .merge(&with_definitions(json!({"Tag": {"type": "string"}, "Id": {"type": "number"}})))
+            .unwrap();
+        let names: Vec<&String> = merged.modifiers().definitions.keys().collect();
+        assert_eq!(names, ["Tag", "Id"]);
+        let error = tag.merge(&with_definitions(json!({"Tag": {"type": "number"}}))).unwrap_err();
+        assert_eq!(error.to_string(), "Definition 'Tag' differs between the combined schemas");
+        let error = serde_json::from_value::<Schema>(json!({
+            "type": "compose",
+            "schema": {"type": "object", "shape": {}, "definitions": {"Tag": {"type": "string"}}},
+            "steps": [{"op": "merge", "schema": {"type": "object", "shape": {}, "definitions": {"Tag": {"type": "string", "min": 1}}}}]
+        }))
+        .unwrap_err();
+        assert_eq!(error.to_string(), "Definition 'Tag' differs between the combined schemas");
+    }
+
+    #[test]
+    fn test_intersection_schema() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "intersection",
+            "options": [
+                {"type": "object", "shape": {"name": {"type": "string"}}},
+                {"type": "object", "shape": {"role": {"type": "string", "default": "member"}}}
+            ]
+        }))
+        .unwrap();
+
+        let output = Validator::parse(&schema, &json!({"name": "Ada", "extra": true})).unwrap();
+        assert_eq!(output, json!({"name": "Ada", "role": "member"}));
+
+        let errors = Validator::validate(&schema, &json!({"role": 1})).unwrap_err();
+        let codes: Vec<&str> = errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, ["required", "invalid_type"]);
+
+        let errors = Validator::validate_at_path(&schema, &json!({"name": "Ada", "role": 1}), &["role".to_string()]).unwrap_err();
+        assert_eq!(errors[0].path, vec!["role"]);
+
+        // A transformed field that another option passes through cannot be merged
+        let conflicting: Schema = serde_json::from_value(json!({
+            "type": "intersection",
+            "options": [
+                {"type": "string", "trim": true},
+                {"type": "string"}
+            ]
+        }))
+        .unwrap();
+        assert!(Validator::validate(&conflicting, &json!("ok")).is_ok());
+        let errors = Validator::validate(&conflicting, &json!(" ok ")).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_intersection");
+        assert_eq!(errors[0].message, "Intersection results could not be merged");
+    }
+
+    #[test]
+    fn test_validate_at_array_path() {
+        let mut item_shape = IndexMap::new();
+        item_shape.insert(
+            "price".to_string(),
+            Schema::Number(NumberSchema {
+                positive: Some(true),
+                ..Default::default()
+            }),
+        );
+
+        let mut shape = IndexMap::new();
+        shape.insert(
+            "items".to_string(),
+            Schema::Array(ArraySchema::new(Schema::Object(ObjectSchema {
+                shape: item_shape,
+                ..Default::default()
+            }))),
+        );
+
+        let schema = Schema::Object(ObjectSchema { shape, ..Default::default() });
+        let value = json!({"items": [{"price": 1}, {"price": 2}, {"price": 3}, {"price": -1}]});
+        let path: Vec<String> = ["items", "3", "price"].iter().map(|s| s.to_string()).collect();
+
+        let errors = Validator::validate_at_path(&schema, &value, &path).unwrap_err();
+        assert_eq!(errors[0].path, path);
+        assert_eq!(errors[0].code, "number.positive");
+
+        let path: Vec<String> = ["items", "0", "price"].iter().map(|s| s.to_string()).collect();
+        assert!(Validator::validate_at_path(&schema, &value, &path).is_ok());
+
+        let path: Vec<String> = ["items", "first"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            Validator::validate_at_path(&schema, &value, &path).unwrap_err()[0].code,
+            "invalid_path"
+        );
+    }
+
+    #[test]
+    fn test_optional_nullable_and_default() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "name": {"type": "string"},
+                "nickname": {"type": "string", "optional": true},
+                "middle_name": {"type": "string", "nullable": true},
+                "role": {"type": "string", "default": "member"}
+            }
+        }))
+        .unwrap();
+
+        let output = Validator::parse(&schema, &json!({"name": "Ada", "middle_name": null})).unwrap();
+        assert_eq!(output, json!({"name": "Ada", "middle_name": null, "role": "member"}));
+
+        let output = Validator::parse(&schema, &json!({"name": "Ada", "middle_name": "B", "role": "admin"})).unwrap();
+        assert_eq!(output["role"], "admin");
+
+        // Optional does not imply nullable
+        let errors = Validator::validate(&schema, &json!({"name": "Ada", "nickname": null, "middle_name": null})).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["nickname"]);
+        assert_eq!(errors[0].code, "invalid_type");
+
+        // Nullable does not imply optional
+        let errors = Validator::validate(&schema, &json!({"name": "Ada"})).unwrap_err();
+        assert_eq!(errors[0].path, vec!["middle_name"]);
+        assert_eq!(errors[0].code, "required");
+
+        assert!(Validator::validate_at_path(&schema, &json!({}), &["nickname".to_string()]).is_ok());
+        assert!(Validator::validate_at_path(&schema, &json!({}), &["role".to_string()]).is_ok());
+        assert!(Validator::validate_at_path(&schema, &json!({}), &["name".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_strips_unknown_keys() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "name": {"type": "string"},
+                "tags": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "shape": {"id": {"type": "number"}, "primary": {"type": "boolean", "default": false}}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let input = json!({
+            "name": "Ada",
+            "emial": "typo@example.com",
+            "tags": [{"id": 1, "internal": true}, {"id": 2, "primary": true}]
+        });
+        let output = Validator::parse(&schema, &input).unwrap();
+        assert_eq!(
+            output,
+            json!({"name": "Ada", "tags": [{"id": 1, "primary": false}, {"id": 2, "primary": true}]})
+        );
+
+        // Validation alone is unaffected by extra keys
+        assert!(Validator::validate(&schema, &input).is_ok());
+
+        let errors = Validator::parse(&schema, &json!({"name": 1, "tags": []})).unwrap_err();
+        assert_eq!(errors[0].path, vec!["name"]);
+    }
+
+    #[test]
+    fn test_unknown_keys_policies() {
+        let schema_with = |unknown_keys: Value| -> Schema {
+            serde_json::from_value(json!({
+                "type": "object",
+                "shape": {"email": {"type": "string"}},
+                "unknown_keys": unknown_keys
+            }))
+            .unwrap()
+        };
+        let input = json!({"email": "a@b.c", "emial": "typo", "age": 3});
+
+        let output = Validator::parse(&schema_with(json!("strip")), &input).unwrap();
+        assert_eq!(output, json!({"email": "a@b.c"}));
+
+        let errors = Validator::validate(&schema_with(json!("strict")), &input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "unrecognized_keys");
+        assert!(errors[0].path.is_empty());
+        assert_eq!(errors[0].params["keys"], json!(["emial", "age"]));
+        assert_eq!(errors[0].message, "Unrecognized key(s) in object: 'emial' | 'age'");
+        assert!(Validator::validate(&schema_with(json!("strict")), &json!({"email": "a"})).is_ok());
+
+        let output = Validator::parse(&schema_with(json!("passthrough")), &input).unwrap();
+        assert_eq!(output, input);
+
+        let catchall = schema_with(json!({"catchall": {"type": "number"}}));
+        let errors = Validator::validate(&catchall, &input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["emial"]);
+        assert_eq!(errors[0].code, "invalid_type");
+
+        let output = Validator::parse(&catchall, &json!({"email": "a", "age": 3})).unwrap();
+        assert_eq!(output, json!({"email": "a", "age": 3}));
+        assert!(Validator::validate_at_path(&catchall, &input, &["age".to_string()]).is_ok());
+        assert!(Validator::validate_at_path(&catchall, &input, &["emial".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_coercion() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "age": {"type": "number", "coerce": true, "integer": true},
+                "price": {"type": "number", "coerce": true},
+                "subscribe": {"type": "boolean", "coerce": true},
+                "terms": {"type": "boolean", "coerce": true},
+                "nickname": {"type": "string", "coerce": true, "optional": true},
+                "count": {"type": "number", "coerce": true, "default": 1}
+            }
+        }))
+        .unwrap();
+
+        let input = json!({
+            "age": " 42 ", "price": "9.5", "subscribe": "on", "terms": "0", "nickname": "", "count": ""
+        });
+        let output = Validator::parse(&schema, &input).unwrap();
+        assert_eq!(
+            output,
+            json!({"age": 42, "price": 9.5, "subscribe": true, "terms": false, "count": 1})
+        );
+
+        let input = json!({"age": "4.5", "price": "abc", "subscribe": "maybe", "terms": true});
+        let errors = Validator::validate(&schema, &input).unwrap_err();
+        assert_eq!(errors[0].code, "number.integer");
+        assert_eq!(errors[1].path, vec!["price"]);
+        assert_eq!(errors[1].code, "coercion_failed");
+        assert_eq!(errors[1].params["expected"], "number");
+        assert_eq!(errors[1].message, "Cannot convert 'abc' to number");
+        assert_eq!(errors[2].code, "coercion_failed");
+
+        // Empty input is missing, so a required field reports `required`
+        let errors = Validator::validate_at_path(&schema, &json!({"age": ""}), &["age".to_string()]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "required");
+        assert_eq!(errors[0].path, vec!["age"]);
+        let errors = Validator::validate(&schema, &json!({"age": "", "price": 1, "subscribe": true, "terms": true})).unwrap_err();
+        assert_eq!(errors[0].code, "required");
+        assert!(Validator::validate_at_path(&schema, &json!({"nickname": ""}), &["nickname".to_string()]).is_ok());
+        assert!(Validator::validate_at_path(&schema, &json!({"count": ""}), &["count".to_string()]).is_ok());
+
+        // The global option applies unless a node opts out
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "age": {"type": "number"},
+                "code": {"type": "number", "coerce": false}
+            }
+        }))
+        .unwrap();
+        let options = ValidationOptions { coerce: true, ..Default::default() };
+        let input = json!({"age": "7", "code": "7"});
+        let errors = Validator::validate_with_options(&schema, &input, &options).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["code"]);
+        assert_eq!(errors[0].code, "invalid_type");
+        assert!(Validator::validate(&schema, &input).is_err());
+
+        // A reference coerces through to the node it refers to
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "object",
+            "shape": {
+                "quantity": {"type": "ref", "name": "Quantity", "coerce": true},
+                "limit": {"type": "ref", "name": "Limit"}
+            },
+            "definitions": {
+                "Quantity": {"type": "number", "integer": true},
+                "Limit": {"type": "number", "coerce": true}
+            }
+        }))
+        .unwrap();
+        let output = Validator::parse(&schema, &json!({"quantity": "3", "limit": "10"})).unwrap();
+        assert_eq!(output, json!({"quantity": 3, "limit": 10}));
+        let errors = Validator::validate(&schema, &json!({"quantity": "1.5", "limit": "x"})).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, "number.integer");
+        assert_eq!(errors[1].path, vec!["limit"]);
+        assert_eq!(errors[1].code, "coercion_failed");
+        assert_eq!(errors[1].params["expected"], "number");
+        let errors = Validator::validate_at_path(&schema, &json!({"quantity": ""}), &["quantity".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "required");
+    }
+
+    #[test]
+    fn test_string_transforms() {
+        use crate::schema::Normalization;
+
+        let schema = Schema::String(StringSchema {
+            trim: Some(true),
+            to_lower_case: Some(true),
+            email: Some(true),
+            max: Some(16),
+            ..Default::default()
+        });
+        assert_eq!(Validator::parse(&schema, &json!("  Ada@Example.COM \n")).unwrap(), json!("ada@example.com"));
+
+        // Checks see the transformed value, including interpolated `{value}`
+        let errors = Validator::validate(&schema, &json!(" Not-An-Email ")).unwrap_err();
+        assert_eq!(errors[0].code, "string.email");
+        let schema = Schema::String(StringSchema {
+            trim: Some(true),
+            to_upper_case: Some(true),
+            min: Some(3),
+            messages: Some(crate::schema::StringMessages {
+                min: Some("'{value}' is too short".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let errors = Validator::validate(&schema, &json!("  ab  ")).unwrap_err();
+        assert_eq!(errors[0].message, "'AB' is too short");
+
+        let schema = Schema::String(StringSchema {
+            normalize: Some(Normalization::Nfc),
+            ..Default::default()
+        });
+        assert_eq!(Validator::parse(&schema, &json!("Jose\u{301}")).unwrap(), json!("Jos\u{e9}"));
+
+        let schema = Schema::String(StringSchema {
+            normalize: Some(Normalization::Nfkc),
+            ..Default::default()
+        });
+        assert_eq!(Validator::parse(&schema, &json!("\u{ff21}\u{fb01}")).unwrap(), json!("Afi"));
+    }
+
+    #[test]
+    fn test_string_length_units() {
+        use crate::schema::LengthUnit;
+
+        let schema_with = |unit: Option<LengthUnit>| {
+            Schema::String(StringSchema {
+                min: Some(4),
+                max: Some(4),
+                length_unit: unit,
+                ..Default::default()
+            })
+        };
+
+        // "José" is 5 UTF-8 bytes but 4 characters
+        assert!(Validator::validate(&schema_with(None), &json!("José")).is_ok());
+
+        // A family emoji is 1 grapheme, 5 scalar values and 8 UTF-16 code units
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let errors = Validator::validate(&schema_with(None), &json!(family)).unwrap_err();
+        assert_eq!(errors[0].code, "string.max");
+        assert_eq!(errors[0].params["actual"], 5);
+        assert_eq!(errors[0].params["unit"], "code_points");
+
+        let errors = Validator::validate(&schema_with(Some(LengthUnit::Graphemes)), &json!(family)).unwrap_err();
+        assert_eq!(errors[0].code, "string.min");
+        assert_eq!(errors[0].params["actual"], 1);
+        assert_eq!(errors[0].params["unit"], "graphemes");
+
+        let errors = Validator::validate(&schema_with(Some(LengthUnit::Utf16)), &json!(family)).unwrap_err();
+        assert_eq!(errors[0].params["actual"], 8);
+        assert_eq!(errors[0].params["unit"], "utf16");
+        assert!(Validator::validate(&schema_with(Some(LengthUnit::Utf16)), &json!("ab\u{1f600}")).is_ok());
+    }
 
     #[test]
-    fn test_string_validation() {
-        let schema = Schema::String {
-            min: Some(3),
-            max: Some(10),
-            email: None,
-            url: None,
-            pattern: None,
-            ui: None,
-            messages: None,
+    fn test_string_formats() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            (
+                "uuid",
+                &["123e4567-e89b-12d3-a456-426614174000", "00000000-0000-0000-0000-000000000000"],
+                &["123e4567-e89b-12d3-a456-42661417400", "123e4567-e89b-02d3-a456-426614174000"],
+            ),
+            ("ulid", &["01ARZ3NDEKTSV4RRFFQ69G5FAV"], &["01ARZ3NDEKTSV4RRFFQ69G5FAU", "81ARZ3NDEKTSV4RRFFQ69G5FAV"]),
+            ("cuid", &["cjld2cjxh0000qzrmn831i7rn"], &["jld2cjxh0000qzrmn831i7rn", "c123"]),
+            ("ipv4", &["192.168.0.1"], &["256.0.0.1", "1.2.3"]),
+            ("ipv6", &["::1", "2001:db8::8a2e:370:7334"], &["2001:db8::g", "1.2.3.4"]),
+            ("cidr", &["10.0.0.0/8", "2001:db8::/32"], &["10.0.0.0/33", "10.0.0.0", "10.0.0.0/08"]),
+            ("hostname", &["example.com", "localhost", "a-b.example."], &["-bad.com", "a..b", "under_score.com"]),
+            ("date", &["2024-02-29", "1999-12-31"], &["2023-02-29", "2024-13-01", "2024-1-01"]),
+            ("time", &["09:30", "23:59:59", "12:00:00.123"], &["24:00", "12:60", "12:00:00."]),
+            (
+                "datetime",
+                &["2024-01-15T09:30:00Z", "2024-01-15T09:30:00.5+05:30", "2024-01-15t09:30-08:00"],
+                &["2024-01-15T09:30:00", "2024-01-15 09:30:00Z", "2024-01-15T09:30:00+5:30"],
+            ),
+            ("duration", &["P1Y2M3DT4H5M6S", "PT0.5S", "P2W"], &["P", "PT", "P1H", "1Y"]),
+            ("base64", &["aGVsbG8=", "aGk=", ""], &["aGVsbG8", "aGk===", "a$==" ]),
+            ("hex", &["deadBEEF", "0"], &["0x1f", "xyz", ""]),
+            ("semver", &["1.2.3", "1.0.0-rc.1+build.5"], &["1.2", "01.2.3", "1.2.3-01"]),
+            ("jwt", &["eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.sig-_", "eyJhbGciOiJub25lIn0.e30."], &["a.b", "a..c", "a.b.c+"]),
+        ];
+
+        for (format, valid, invalid) in cases {
+            let schema: Schema = serde_json::from_value(json!({"type": "string", *format: true})).unwrap();
+
+            for s in *valid {
+                assert!(Validator::validate(&schema, &json!(s)).is_ok(), "{} should accept {:?}", format, s);
+            }
+            for s in *invalid {
+                let errors = Validator::validate(&schema, &json!(s)).unwrap_err();
+                assert_eq!(errors[0].code, format!("string.{}", format), "{} should reject {:?}", format, s);
+                assert_eq!(errors[0].params["validation"], *format);
+            }
+        }
+
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "string",
+            "uuid": true,
+            "messages": {"uuid": "{label} must be a UUID"}
+        }))
+        .unwrap();
+        assert_eq!(Validator::validate(&schema, &json!("x")).unwrap_err()[0].message, "value must be a UUID");
+    }
+
+    #[test]
+    fn test_email_rules() {
+        let email = |extra: Value| -> Schema {
+            let mut schema = json!({"type": "string", "email": true});
+            schema.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(schema).unwrap()
+        };
+        let reason = |schema: &Schema, s: &str| {
+            Validator::validate(schema, &json!(s)).unwrap_err()[0].params["reason"].clone()
         };
 
-        assert!(Validator::validate(&schema, &json!("hello")).is_ok());
-        assert!(Validator::validate(&schema, &json!("hi")).is_err());
-        assert!(Validator::validate(&schema, &json!("verylongstring")).is_err());
+        let schema = email(json!({}));
+        for valid in ["a.b+tag@example.co.uk", "\"john doe\"@example.com", "user@[192.168.0.1]", "用户@例子.广告"] {
+            assert!(Validator::validate(&schema, &json!(valid)).is_ok(), "{}", valid);
+        }
+        assert_eq!(reason(&schema, "a@b"), "missing_tld");
+        assert_eq!(reason(&schema, "x@."), "syntax");
+        assert_eq!(reason(&schema, "a..b@example.com"), "syntax");
+        assert_eq!(reason(&schema, "no-at-sign"), "syntax");
+        assert_eq!(reason(&schema, &format!("{}@example.com", "a".repeat(65))), "local_part_too_long");
+        assert_eq!(reason(&schema, &format!("a@{}.com", "b".repeat(250))), "too_long");
+
+        assert!(Validator::validate(&email(json!({"require_tld": false})), &json!("root@localhost")).is_ok());
+        assert_eq!(reason(&email(json!({"allow_ip": false})), "user@[192.168.0.1]"), "ip_host");
+        assert_eq!(reason(&email(json!({"allow_idn": false})), "user@例子.广告"), "idn");
+        assert_eq!(reason(&email(json!({"allow_idn": false})), "user@xn--fsqu00a.xn--4rr70v"), "idn");
+
+        let short = email(json!({"max_length": 16}));
+        assert!(Validator::validate(&short, &json!("ab@example.com")).is_ok());
+        assert_eq!(reason(&short, "abcd@example.com1"), "too_long");
+        let long = format!("{}@{}com", "a".repeat(64), format!("{}.", "b".repeat(49)).repeat(4));
+        assert_eq!(reason(&schema, &long), "too_long");
+        assert!(Validator::validate(&email(json!({"max_length": 320})), &json!(long)).is_ok());
     }
 
     #[test]
-    fn test_email_validation() {
-        let schema = Schema::String {
-            min: None,
-            max: None,
-            email: Some(true),
-            url: None,
-            pattern: None,
-            ui: None,
-            messages: None,
+    fn test_url_rules() {
+        let url = |extra: Value| -> Schema {
+            let mut schema = json!({"type": "string", "url": true});
+            schema.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(schema).unwrap()
+        };
+        let reason = |schema: &Schema, s: &str| {
+            Validator::validate(schema, &json!(s)).unwrap_err()[0].params["reason"].clone()
         };
 
-        assert!(Validator::validate(&schema, &json!("test@example.com")).is_ok());
-        assert!(Validator::validate(&schema, &json!("invalid")).is_err());
+        let schema = url(json!({}));
+        for valid in ["https://example.com/a?b#c", "http://localhost:3000", "ftp://files.example.org", "http://[::1]/", "https://münchen.de"] {
+            assert!(Validator::validate(&schema, &json!(valid)).is_ok(), "{}", valid);
+        }
+        assert_eq!(reason(&schema, "https://"), "syntax");
+        assert_eq!(reason(&schema, "not a url"), "syntax");
+        assert_eq!(reason(&schema, "htp://invalid"), "scheme");
+        assert_eq!(reason(&schema, "mailto:a@example.com"), "scheme");
+
+        let schema = url(json!({"schemes": ["mailto", "HTTPS"]}));
+        assert!(Validator::validate(&schema, &json!("mailto:a@example.com")).is_ok());
+        assert!(Validator::validate(&schema, &json!("https://example.com")).is_ok());
+        assert_eq!(reason(&schema, "http://example.com"), "scheme");
+
+        assert_eq!(reason(&url(json!({"require_tld": true})), "http://localhost:3000"), "missing_tld");
+        assert_eq!(reason(&url(json!({"allow_ip": false})), "http://127.0.0.1/"), "ip_host");
+        assert_eq!(reason(&url(json!({"allow_idn": false})), "https://münchen.de"), "idn");
+        // Only the host is checked for IDNs, not the path or query
+        for ascii_host in ["https://example.com/café", "https://example.com/?q=ü#straße"] {
+            assert!(Validator::validate(&url(json!({"allow_idn": false})), &json!(ascii_host)).is_ok(), "{}", ascii_host);
+        }
+
+        let long = format!("https://example.com/{}", "a".repeat(2048));
+        assert!(Validator::validate(&schema, &json!(long)).is_ok());
+        let short = url(json!({"max_length": 2048}));
+        assert_eq!(reason(&short, &long), "too_long");
+        assert!(Validator::validate(&short, &json!("https://example.com/a")).is_ok());
     }
 
     #[test]
-    fn test_number_validation() {
-        let schema = Schema::Number {
-            min: Some(0.0),
-            max: Some(100.0),
-            integer: Some(true),
-            positive: None,
-            ui: None,
-            messages: None,
+    fn test_datetime_offsets() {
+        let schema_with = |offset: &str| -> Schema {
+            serde_json::from_value(json!({"type": "string", "datetime": true, "offset": offset})).unwrap()
         };
 
-        assert!(Validator::validate(&schema, &json!(50)).is_ok());
-        assert!(Validator::validate(&schema, &json!(-1)).is_err());
-        assert!(Validator::validate(&schema, &json!(50.5)).is_err());
+        let utc = schema_with("utc");
+        assert!(Validator::validate(&utc, &json!("2024-01-15T09:30:00Z")).is_ok());
+        assert!(Validator::validate(&utc, &json!("2024-01-15T09:30:00+01:00")).is_err());
+
+        let local = schema_with("local");
+        assert!(Validator::validate(&local, &json!("2024-01-15T09:30")).is_ok());
+        assert!(Validator::validate(&local, &json!("2024-01-15T09:30Z")).is_err());
+
+        let optional = schema_with("optional");
+        assert!(Validator::validate(&optional, &json!("2024-01-15T09:30")).is_ok());
+        assert!(Validator::validate(&optional, &json!("2024-01-15T09:30-03:00")).is_ok());
+        assert!(Validator::validate(&optional, &json!("2024-01-15T09:30+3")).is_err());
     }
 
     #[test]
-    fn test_object_validation() {
-        let mut shape = HashMap::new();
-        shape.insert(
-            "name".to_string(),
-            Schema::String {
-                min: Some(1),
-                max: None,
-                email: None,
-                url: None,
-                pattern: None,
-                ui: None,
-                messages: None,
-            },
-        );
-        shape.insert(
-            "age".to_string(),
-            Schema::Number {
-                min: Some(0.0),
-                max: None,
-                integer: Some(true),
-                positive: Some(true),
-                ui: None,
-                messages: None,
-            },
-        );
+    fn test_number_refinements() {
+        let codes = |schema: Value, value: Value| {
+            error_codes(Validator::validate(&serde_json::from_value(schema).unwrap(), &value))
+        };
 
-        let schema = Schema::Object { shape, ui: None, messages: None };
+        let bounds = json!({"type": "number", "exclusive_min": 0, "exclusive_max": 10});
+        assert!(codes(bounds.clone(), json!(5)).is_empty());
+        assert_eq!(codes(bounds.clone(), json!(0)), ["number.exclusive_min"]);
+        assert_eq!(codes(bounds, json!(10)), ["number.exclusive_max"]);
 
-        assert!(Validator::validate(&schema, &json!({"name": "John", "age": 30})).is_ok());
-        assert!(Validator::validate(&schema, &json!({"name": "", "age": 30})).is_err());
-        assert!(Validator::validate(&schema, &json!({"name": "John"})).is_err());
+        let cents = json!({"type": "number", "multiple_of": 0.01});
+        for valid in [json!(19.99), json!(0.3), json!(1234567.89), json!(-0.07), json!(5)] {
+            assert!(codes(cents.clone(), valid.clone()).is_empty(), "{}", valid);
+        }
+        assert_eq!(codes(cents, json!(19.999)), ["number.multiple_of"]);
+        assert_eq!(codes(json!({"type": "number", "multiple_of": 5}), json!(12)), ["number.multiple_of"]);
+
+        assert_eq!(codes(json!({"type": "number", "negative": true}), json!(0)), ["number.negative"]);
+        assert!(codes(json!({"type": "number", "non_negative": true}), json!(0)).is_empty());
+        assert_eq!(codes(json!({"type": "number", "non_negative": true}), json!(-1)), ["number.non_negative"]);
+        assert_eq!(codes(json!({"type": "number", "non_positive": true}), json!(0.5)), ["number.non_positive"]);
+
+        let safe = json!({"type": "number", "safe_integer": true});
+        assert!(codes(safe.clone(), json!(9_007_199_254_740_991_i64)).is_empty());
+        assert_eq!(codes(safe.clone(), json!(9_007_199_254_740_992_i64)), ["number.safe_integer"]);
+        assert_eq!(codes(safe, json!(1.5)), ["number.safe_integer"]);
+
+        // Literals beyond the f64 range are out of range, not of the wrong type
+        let huge: Value = serde_json::from_str("1e400").unwrap();
+        let finite = json!({"type": "number", "finite": true});
+        assert_eq!(codes(finite, huge.clone()), ["number.finite"]);
+        assert_eq!(codes(json!({"type": "number", "max": 10}), huge), ["number.max"]);
+        let negative: Value = serde_json::from_str("-1e400").unwrap();
+        assert_eq!(codes(json!({"type": "number", "min": 0}), negative), ["number.min"]);
+
+        let error = Validator::validate(
+            &serde_json::from_value(json!({"type": "number", "multiple_of": 0.25})).unwrap(),
+            &json!(1.1),
+        )
+        .unwrap_err()
+        .remove(0);
+        assert_eq!(error.params["multiple_of"], json!(0.25));
+        assert_eq!(error.message, "Number must be a multiple of 0.25");
     }
 
     #[test]
-    fn test_validate_at_path() {
-        let mut shape = HashMap::new();
-        shape.insert(
-            "email".to_string(),
-            Schema::String {
-                min: Some(3),
-                max: None,
-                email: Some(true),
-                url: None,
-                pattern: None,
-                ui: None,
-                messages: None,
-            },
-        );
+    fn test_decimal_schema() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"type": "decimal", "precision": 6, "scale": 2, "min": "0.01", "max": 9999.99}"#,
+        )
+        .unwrap();
+        let codes = |input: &str| error_codes(Validator::validate(&schema, &serde_json::from_str(input).unwrap()));
 
-        let schema = Schema::Object { shape, ui: None, messages: None };
-        let value = json!({"email": "test@example.com"});
+        for valid in ["0.01", "19.90", "\"9999.99\"", "1.5e2", "\"0042.10\""] {
+            assert!(codes(valid).is_empty(), "{}", valid);
+        }
+        assert_eq!(codes("0.001"), ["decimal.scale", "decimal.min"]);
+        assert_eq!(codes("10000.00"), ["decimal.precision", "decimal.max"]);
+        assert_eq!(codes("9999.991"), ["decimal.precision", "decimal.scale", "decimal.max"]);
+        assert_eq!(codes("\"12,50\""), ["invalid_type"]);
+        assert_eq!(codes("true"), ["invalid_type"]);
 
-        assert!(Validator::validate_at_path(&schema, &value, &["email".to_string()]).is_ok());
-        
-        let invalid_value = json!({"email": "invalid"});
-        assert!(Validator::validate_at_path(&schema, &invalid_value, &["email".to_string()]).is_err());
+        // 0.1 + 0.2 in f64 is 0.30000000000000004, which must not pass as 0.3
+        let schema: Schema = serde_json::from_value(json!({"type": "decimal", "max": "0.3"})).unwrap();
+        let value: Value = serde_json::from_str("0.30000000000000004").unwrap();
+        let error = Validator::validate(&schema, &value).unwrap_err().remove(0);
+        assert_eq!(error.code, "decimal.max");
+        assert_eq!(error.params["actual"], json!("0.30000000000000004"));
+        assert_eq!(error.message, "Number must be at most 0.3");
     }
 
     #[test]
-    fn test_custom_string_messages() {
-        use crate::schema::StringMessages;
-        
-        let messages = StringMessages {
-            invalid_type: Some("Custom: not a string".to_string()),
-            min: Some("Custom: too short".to_string()),
-            max: Some("Custom: too long".to_string()),
-            email: Some("Custom: bad email".to_string()),
-            url: None,
-            pattern: None,
+    fn test_bigint_schema() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"type": "bigint", "min": 0, "max": "18446744073709551615"}"#,
+        )
+        .unwrap();
+        let check = |input: &str| {
+            let value: Value = serde_json::from_str(input).unwrap();
+            Validator::parse(&schema, &value).map_err(|errors| errors[0].code.clone())
         };
 
-        let schema = Schema::String {
-            min: Some(5),
-            max: Some(10),
-            email: Some(true),
-            url: None,
-            pattern: None,
-            ui: None,
-            messages: Some(messages),
+        let id = check("18446744073709551615").unwrap();
+        assert_eq!(id.to_string(), "18446744073709551615");
+        assert!(check("\"9007199254740993\"").is_ok());
+        assert!(check("1e3").is_ok());
+        assert_eq!(check("18446744073709551616").unwrap_err(), "bigint.max");
+        assert_eq!(check("\"-1\"").unwrap_err(), "bigint.min");
+        assert_eq!(check("1.5").unwrap_err(), "invalid_type");
+
+        // Numbers in the schema survive parsing from text
+        let schema: Schema = serde_json::from_str(r#"{"type": "number", "min": 5, "default": 1.5}"#).unwrap();
+        assert_eq!(schema.modifiers().default, Some(json!(1.5)));
+        assert!(Validator::validate(&schema, &json!(4)).is_err());
+    }
+
+    #[test]
+    fn test_date_schema() {
+        // 2024-06-15T12:00:00Z
+        let options = ValidationOptions { now: Some(1_718_452_800_000), ..Default::default() };
+        let codes = |schema: Value, value: Value| {
+            error_codes(Validator::validate_with_options(&serde_json::from_value(schema).unwrap(), &value, &options))
         };
 
-        // Test invalid type
-        let result = Validator::validate(&schema, &json!(123));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert_eq!(errors[0].message, "Custom: not a string");
+        let check_out = json!({"type": "date", "min": "now+P1D", "max": "2024-12-31"});
+        assert!(codes(check_out.clone(), json!("2024-06-16")).is_empty());
+        assert_eq!(codes(check_out.clone(), json!("2024-06-15")), ["date.min"]);
+        assert_eq!(codes(check_out.clone(), json!("2025-01-01")), ["date.max"]);
+        assert_eq!(codes(check_out.clone(), json!("2024-02-30")), ["date.invalid"]);
+        assert_eq!(codes(check_out, json!(1_718_452_800_000_i64)), ["invalid_type"]);
 
-        // Test min length
-        let result = Validator::validate(&schema, &json!("abc"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: too short"));
+        let adult = json!({"type": "date", "min_age": 18, "max_age": 120});
+        assert!(codes(adult.clone(), json!("2006-06-15")).is_empty());
+        assert_eq!(codes(adult.clone(), json!("2006-06-16")), ["date.min_age"]);
+        assert_eq!(codes(adult, json!("1903-06-14")), ["date.max_age"]);
 
-        // Test max length
-        let result = Validator::validate(&schema, &json!("verylongstring"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: too long"));
+        // Today is already the 16th at +09:00, and epoch input lands on that date
+        let tokyo = json!({"type": "date", "max": "now", "utc_offset": "+09:00", "epoch_millis": true});
+        assert!(codes(tokyo.clone(), json!("2024-06-15")).is_empty());
+        assert!(codes(tokyo.clone(), json!(1_718_452_800_000_i64)).is_empty());
+        assert_eq!(codes(tokyo, json!("2024-06-17")), ["date.max"]);
 
-        // Test email
-        let result = Validator::validate(&schema, &json!("hello"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: bad email"));
+        let schema: Schema = serde_json::from_value(json!({"type": "date", "min": "now-P1M"})).unwrap();
+        let error = Validator::validate_with_options(&schema, &json!("2024-05-01"), &options).unwrap_err().remove(0);
+        assert_eq!(error.params["minimum"], json!("2024-05-15"));
+        assert_eq!(error.message, "Date must be on or after 2024-05-15");
+
+        assert!(serde_json::from_value::<Schema>(json!({"type": "date", "min": "yesterday"})).is_err());
     }
 
     #[test]
-    fn test_custom_number_messages() {
-        use crate::schema::NumberMessages;
-        
-        let messages = NumberMessages {
-            invalid_type: Some("Custom: not a number".to_string()),
-            min: Some("Custom: too small".to_string()),
-            max: Some("Custom: too large".to_string()),
-            integer: Some("Custom: must be whole number".to_string()),
-            positive: Some("Custom: must be positive".to_string()),
-        };
+    fn test_date_bound_shift_range() {
+        let options = ValidationOptions { now: Some(1_718_452_800_000), ..Default::default() };
+        let bound = |min: &str| serde_json::from_value::<Schema>(json!({"type": "datetime", "min": min}));
 
-        let schema = Schema::Number {
-            min: Some(10.0),
-            max: Some(100.0),
-            integer: Some(true),
-            positive: Some(true),
-            ui: None,
-            messages: Some(messages),
+        for overflowing in [
+            "now+P999999999999999999Y",
+            "now+P9223372036854775807W",
+            "now+P9223372036854775807D",
+            "now-PT9223372036854775807H",
+            "now+P10001Y",
+            "now+P3652426D",
+        ] {
+            let error = bound(overflowing).unwrap_err().to_string();
+            assert!(error.starts_with("invalid date bound"), "{}: {}", overflowing, error);
+        }
+
+        let schema = bound("now-P10000Y").unwrap();
+        assert!(Validator::validate_with_options(&schema, &json!("0001-01-01T00:00:00Z"), &options).is_ok());
+        let schema: Schema = serde_json::from_value(json!({"type": "date", "max": "now+P7000Y"})).unwrap();
+        let error = Validator::validate_with_options(&schema, &json!("9999-12-31"), &options).unwrap_err();
+        assert_eq!(error[0].params["maximum"], json!("9024-06-15"));
+
+        // `now` itself may sit where no shift can take it
+        let extreme = ValidationOptions { now: Some(i64::MAX), ..Default::default() };
+        let schema: Schema = serde_json::from_value(json!({"type": "date", "max": "now+P10000Y"})).unwrap();
+        assert!(Validator::validate_with_options(&schema, &json!("2024-06-15"), &extreme).is_ok());
+    }
+
+    #[test]
+    fn test_datetime_schema() {
+        let options = ValidationOptions { now: Some(1_718_452_800_000), ..Default::default() };
+        let codes = |schema: Value, value: Value| {
+            error_codes(Validator::validate_with_options(&serde_json::from_value(schema).unwrap(), &value, &options))
         };
 
-        // Test invalid type
-        let result = Validator::validate(&schema, &json!("not a number"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert_eq!(errors[0].message, "Custom: not a number");
+        let future = json!({"type": "datetime", "min": "now+PT1H", "epoch_millis": true});
+        assert!(codes(future.clone(), json!("2024-06-15T13:00:00Z")).is_empty());
+        assert!(codes(future.clone(), json!("2024-06-15T15:00:00+02:00")).is_empty());
+        assert_eq!(codes(future.clone(), json!("2024-06-15T14:59:59+02:00")), ["date.min"]);
+        assert_eq!(codes(future.clone(), json!(1_718_452_800_000_i64)), ["date.min"]);
+        assert_eq!(codes(future, json!("2024-06-15 13:00")), ["date.invalid"]);
 
-        // Test min value
-        let result = Validator::validate(&schema, &json!(5));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: too small"));
+        let utc = json!({"type": "datetime", "offset": "utc"});
+        assert!(codes(utc.clone(), json!("2024-06-15T13:00:00Z")).is_empty());
+        assert_eq!(codes(utc, json!("2024-06-15T13:00:00+01:00")), ["date.offset"]);
 
-        // Test max value
-        let result = Validator::validate(&schema, &json!(150));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: too large"));
+        // Local input is read at the schema's offset
+        let local = json!({"type": "datetime", "offset": "local", "utc_offset": "-05:00", "max": "2024-06-15T12:00:00Z"});
+        assert!(codes(local.clone(), json!("2024-06-15T07:00")).is_empty());
+        assert_eq!(codes(local.clone(), json!("2024-06-15T07:01")), ["date.max"]);
 
-        // Test integer
-        let result = Validator::validate(&schema, &json!(50.5));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: must be whole number"));
+        let schema: Schema = serde_json::from_value(local).unwrap();
+        let error = Validator::validate_with_options(&schema, &json!("2024-06-15T08:00"), &options).unwrap_err().remove(0);
+        assert_eq!(error.params["maximum"], json!("2024-06-15T12:00:00Z"));
+    }
 
-        // Test positive
-        let result = Validator::validate(&schema, &json!(-10));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert!(errors.iter().any(|e| e.message == "Custom: must be positive"));
+    #[test]
+    fn test_string_content_checks() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "string",
+            "starts_with": "SKU-",
+            "ends_with": "-X",
+            "includes": "00",
+            "excludes": "--",
+            "charset": {"classes": ["upper", "digit"], "chars": "-"}
+        }))
+        .unwrap();
+        let codes = |s: &str| error_codes(Validator::validate(&schema, &json!(s)));
+
+        assert!(codes("SKU-1002-X").is_empty());
+        assert_eq!(codes("ABC-1002-X"), ["string.starts_with"]);
+        assert_eq!(codes("SKU-1002-Y"), ["string.ends_with"]);
+        assert_eq!(codes("SKU-1234-X"), ["string.includes"]);
+        assert_eq!(codes("SKU--1002-X"), ["string.excludes"]);
+
+        let errors = Validator::validate(&schema, &json!("SKU-100a2b a-X")).unwrap_err();
+        assert_eq!(errors[0].code, "string.charset");
+        assert_eq!(errors[0].params["characters"], json!(["a", "b", " "]));
+        assert_eq!(errors[0].message, "String contains characters that are not allowed: 'a' | 'b' | ' '");
     }
 
+    #[cfg(feature = "regex")]
     #[test]
-    fn test_custom_object_messages() {
-        use crate::schema::ObjectMessages;
-        
-        let messages = ObjectMessages {
-            invalid_type: Some("Custom: not an object".to_string()),
-            required: Some("Custom: field is required".to_string()),
-        };
+    fn test_pattern_validation() {
+        let schema = Schema::String(StringSchema {
+            pattern: Some(r"^\d{5}$".to_string()),
+            ..Default::default()
+        });
 
-        let mut shape = HashMap::new();
-        shape.insert(
-            "name".to_string(),
-            Schema::String {
-                min: None,
-                max: None,
-                email: None,
-                url: None,
-                pattern: None,
-                ui: None,
-                messages: None,
-            },
+        assert!(Validator::validate(&schema, &json!("12345")).is_ok());
+        assert!(Validator::validate(&schema, &json!("123456")).is_err());
+        assert!(Validator::validate(&schema, &json!("zip ^\\d{5}$")).is_err());
+
+        let schema = Schema::String(StringSchema {
+            pattern: Some("^sku-[a-z]+$".to_string()),
+            flags: Some("i".to_string()),
+            ..Default::default()
+        });
+
+        assert!(Validator::validate(&schema, &json!("SKU-Abc")).is_ok());
+        assert_eq!(
+            Validator::validate(&schema, &json!("SKU-123")).unwrap_err()[0].code,
+            "string.pattern"
         );
+    }
 
-        let schema = Schema::Object { 
-            shape, 
-            ui: None, 
-            messages: Some(messages) 
-        };
+    #[test]
+    fn test_invalid_pattern_is_schema_error() {
+        let schema = Schema::String(StringSchema {
+            pattern: Some("(unclosed".to_string()),
+            ..Default::default()
+        });
 
-        // Test invalid type
-        let result = Validator::validate(&schema, &json!("not an object"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert_eq!(errors[0].message, "Custom: not an object");
+        if cfg!(feature = "regex") {
+            let errors = Validator::validate(&schema, &json!("anything")).unwrap_err();
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].code, "invalid_schema");
+        }
+    }
 
-        // Test required field
-        let result = Validator::validate(&schema, &json!({}));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert_eq!(errors[0].message, "Custom: field is required");
+    #[test]
+    fn test_enum_validation() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "enum",
+            "values": ["draft", "published", "archived", 0]
+        }))
+        .unwrap();
+
+        assert!(Validator::validate(&schema, &json!("draft")).is_ok());
+        assert!(Validator::validate(&schema, &json!(0.0)).is_ok());
+
+        let errors = Validator::validate(&schema, &json!("deleted")).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_enum_value");
+        assert_eq!(
+            errors[0].message,
+            "Invalid enum value. Expected 'draft' | 'published' | 'archived' | 0, received 'deleted'"
+        );
     }
 
     #[test]
-    fn test_default_messages_when_custom_not_provided() {
-        let schema = Schema::String {
+    fn test_literal_validation() {
+        use crate::schema::{LiteralMessages, LiteralSchema};
+
+        let schema = Schema::Literal(LiteralSchema {
+            value: json!(true),
+            ..Default::default()
+        });
+
+        assert!(Validator::validate(&schema, &json!(true)).is_ok());
+        let errors = Validator::validate(&schema, &json!(false)).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_literal");
+        assert_eq!(errors[0].message, "Invalid literal value, expected true");
+
+        let schema = Schema::Literal(LiteralSchema {
+            value: json!("yes"),
+            messages: Some(LiteralMessages {
+                invalid_literal: Some("You must accept the terms".to_string()),
+            }),
+            ..Default::default()
+        });
+        let errors = Validator::validate(&schema, &json!("no")).unwrap_err();
+        assert_eq!(errors[0].message, "You must accept the terms");
+    }
+
+    #[test]
+    fn test_discriminated_union() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "discriminated_union",
+            "discriminator": "method",
+            "options": [
+                {"type": "object", "shape": {
+                    "method": {"type": "literal", "value": "card"},
+                    "number": {"type": "string", "min": 12}
+                }},
+                {"type": "object", "shape": {
+                    "method": {"type": "literal", "value": "bank"},
+                    "iban": {"type": "string"}
+                }}
+            ]
+        }))
+        .unwrap();
+
+        assert!(Validator::validate(&schema, &json!({"method": "bank", "iban": "DE00"})).is_ok());
+
+        // Only the selected branch reports errors
+        let errors = Validator::validate(&schema, &json!({"method": "card", "number": "123"})).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["number"]);
+        assert_eq!(errors[0].code, "string.min");
+
+        let errors = Validator::validate(&schema, &json!({"method": "wallet"})).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["method"]);
+        assert_eq!(errors[0].code, "invalid_union_discriminator");
+        assert_eq!(errors[0].message, "Invalid discriminator value. Expected 'card' | 'bank'");
+
+        let value = json!({"method": "bank", "iban": 42});
+        let errors = Validator::validate_at_path(&schema, &value, &["iban".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_type");
+
+        // Options and discriminator fields may be references
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "discriminated_union",
+            "discriminator": "method",
+            "options": [{"type": "ref", "name": "card"}, {"type": "ref", "name": "bank"}],
+            "definitions": {
+                "card": {"type": "object", "shape": {
+                    "method": {"type": "literal", "value": "card"},
+                    "number": {"type": "string", "min": 12}
+                }},
+                "bank": {"type": "object", "shape": {
+                    "method": {"type": "ref", "name": "bank_method"},
+                    "iban": {"type": "string"}
+                }},
+                "bank_method": {"type": "enum", "values": ["bank", "sepa"]}
+            }
+        }))
+        .unwrap();
+
+        assert!(Validator::validate(&schema, &json!({"method": "sepa", "iban": "DE00"})).is_ok());
+        let errors = Validator::validate(&schema, &json!({"method": "card", "number": "123"})).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "string.min");
+
+        let errors = Validator::validate(&schema, &json!({"method": "wallet"})).unwrap_err();
+        assert_eq!(errors[0].message, "Invalid discriminator value. Expected 'card' | 'bank' | 'sepa'");
+
+        let value = json!({"method": "bank", "iban": 42});
+        let errors = Validator::validate_at_path(&schema, &value, &["iban".to_string()]).unwrap_err();
+        assert_eq!(errors[0].code, "invalid_type");
+    }
+
+    #[test]
+    fn test_union_reports_closest_option() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "union",
+            "options": [
+                {"type": "string", "email": true},
+                {"type": "object", "shape": {
+                    "street": {"type": "string"},
+                    "city": {"type": "string", "min": 2}
+                }}
+            ]
+        }))
+        .unwrap();
+
+        assert!(Validator::validate(&schema, &json!("a@example.com")).is_ok());
+        assert!(Validator::validate(&schema, &json!({"street": "Main", "city": "Oslo"})).is_ok());
+
+        let errors = Validator::validate(&schema, &json!({"street": "Main", "city": "O"})).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].code, "invalid_union");
+        assert_eq!(errors[1].path, vec!["city"]);
+        assert_eq!(errors[1].code, "string.min");
+
+        let errors = Validator::validate(&schema, &json!("not-an-email")).unwrap_err();
+        assert_eq!(errors[1].code, "string.email");
+    }
+
+    #[test]
+    fn test_errors_follow_declaration_order() {
+        let keys = ["zeta", "alpha", "mike", "bravo", "yankee", "charlie", "xray", "delta"];
+        let shape: IndexMap<String, Schema> = keys
+            .iter()
+            .map(|key| (key.to_string(), Schema::String(StringSchema::default())))
+            .collect();
+        let schema = Schema::Object(ObjectSchema { shape, ..Default::default() });
+
+        let errors = Validator::validate(&schema, &json!({})).unwrap_err();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path[0].as_str()).collect();
+        assert_eq!(paths, keys);
+
+        // Declaration order survives a JSON round trip
+        let json = serde_json::to_string(&schema).unwrap();
+        let schema: Schema = serde_json::from_str(&json).unwrap();
+        let errors = Validator::validate(&schema, &json!({})).unwrap_err();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path[0].as_str()).collect();
+        assert_eq!(paths, keys);
+    }
+
+    #[test]
+    fn test_error_params() {
+        let schema: Schema = serde_json::from_str(r#"{
+            "type": "object",
+            "shape": {
+                "name": {"type": "string", "min": 5},
+                "age": {"type": "number", "max": 120},
+                "email": {"type": "string"}
+            }
+        }"#)
+        .unwrap();
+
+        let errors = Validator::validate(&schema, &json!({"name": "Al", "age": 130.5})).unwrap_err();
+
+        assert_eq!(errors[0].params["minimum"], 5);
+        assert_eq!(errors[0].params["actual"], 2);
+        assert_eq!(errors[1].params["maximum"], 120.0);
+        assert_eq!(errors[1].params["actual"], 130.5);
+        assert_eq!(errors[2].code, "required");
+        assert_eq!(errors[2].params["expected"], "string");
+        assert_eq!(errors[2].params["received"], "undefined");
+
+        let errors = Validator::validate(&schema, &json!({"name": 1, "age": 1, "email": "a"})).unwrap_err();
+        assert_eq!(errors[0].params["expected"], "string");
+        assert_eq!(errors[0].params["received"], "number");
+    }
+
+    #[test]
+    fn test_message_interpolation() {
+        use crate::schema::{StringMessages, UiConfig};
+
+        let schema = Schema::String(StringSchema {
             min: Some(5),
-            max: None,
-            email: None,
-            url: None,
-            pattern: None,
-            ui: None,
-            messages: None,
-        };
+            max: Some(8),
+            ui: Some(UiConfig {
+                label: Some("Username".to_string()),
+                ..Default::default()
+            }),
+            messages: Some(StringMessages {
+                min: Some("{label} must be at least {min} characters, you typed {length}".to_string()),
+                max: Some("'{value}' is longer than {max} at {path}".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
 
-        let result = Validator::validate(&schema, &json!("abc"));
-        assert!(result.is_err());
-        let errors = result.unwrap_err();
-        assert_eq!(errors[0].message, "String must be at least 5 characters");
+        let errors = Validator::validate(&schema, &json!("abc")).unwrap_err();
+        assert_eq!(errors[0].message, "Username must be at least 5 characters, you typed 3");
+
+        let mut shape = IndexMap::new();
+        shape.insert("user".to_string(), schema);
+        let schema = Schema::Object(ObjectSchema { shape, ..Default::default() });
+
+        let errors = Validator::validate(&schema, &json!({"user": "abcdefghij"})).unwrap_err();
+        assert_eq!(errors[0].message, "'abcdefghij' is longer than 8 at user");
+
+        // Default messages are templates too; unknown placeholders are kept
+        let schema = Schema::Number(NumberSchema {
+            min: Some(1.5),
+            messages: Some(crate::schema::NumberMessages {
+                max: Some("{unknown}".to_string()),
+                ..Default::default()
+            }),
+            max: Some(2.0),
+            ..Default::default()
+        });
+        let errors = Validator::validate(&schema, &json!(1)).unwrap_err();
+        assert_eq!(errors[0].message, "Number must be at least 1.5");
+        let errors = Validator::validate(&schema, &json!(3)).unwrap_err();
+        assert_eq!(errors[0].message, "{unknown}");
+    }
+
+    #[test]
+    fn test_locale_messages() {
+        use crate::locale::{register_catalog, Catalog};
+        use crate::schema::StringMessages;
+
+        let schema = Schema::String(StringSchema {
+            min: Some(5),
+            email: Some(true),
+            ..Default::default()
+        });
+        let german = ValidationOptions { locale: "de-CH".to_string(), ..Default::default() };
+
+        let errors = Validator::validate_with_options(&schema, &json!("a@b"), &german).unwrap_err();
+        assert_eq!(errors[0].message, "Muss mindestens 5 Zeichen lang sein");
+
+        // Registered catalogs override single codes and fall back for the rest
+        let mut catalog = Catalog::default();
+        catalog.insert("string.min", "Too short: {length}/{min}");
+        register_catalog("x-test", catalog);
+        let custom = ValidationOptions { locale: "x-test".to_string(), ..Default::default() };
+
+        let errors = Validator::validate_with_options(&schema, &json!("abc"), &custom).unwrap_err();
+        assert_eq!(errors[0].message, "Too short: 3/5");
+        assert_eq!(errors[1].message, "Invalid email address");
+
+        // Per-schema messages win over any catalog
+        let schema = Schema::String(StringSchema {
+            min: Some(5),
+            messages: Some(StringMessages {
+                min: Some("Custom".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let errors = Validator::validate_with_options(&schema, &json!("abc"), &german).unwrap_err();
+        assert_eq!(errors[0].message, "Custom");
     }
 }
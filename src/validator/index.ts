@@ -2,6 +2,7 @@ import type {
   Schema,
   ValidationResult,
   ValidationError,
+  ParseResult,
 } from "../schema/types";
 import type { SchemaBuilder } from "../schema/builders";
 
@@ -17,7 +18,78 @@ export interface WasmModule {
       value_json: string,
       path_json: string,
     ): string;
+    parse(schema_json: string, value_json: string): string;
+    expand_schema(schema_json: string): string;
+    set_locale(locale: string): void;
+    set_coerce(enabled: boolean): void;
+    set_now(millis: number | undefined): void;
+    set_max_depth(depth: number): void;
+    register_locale(locale: string, catalog_json: string): string;
   };
+  CompiledSchema: new (schema_json: string) => CompiledSchemaHandle;
+}
+
+/**
+ * Schema parsed once on the WASM side
+ * Owns WASM memory until free() is called
+ */
+export interface CompiledSchemaHandle {
+  validate(value_json: string): string;
+  validate_at_path(value_json: string, path_json: string): string;
+  parse(value_json: string): string;
+  free(): void;
+}
+
+/**
+ * Compiled handle for a schema object, with the JSON it was compiled from
+ */
+interface CompiledEntry {
+  json: string;
+  handle: CompiledSchemaHandle;
+}
+
+/**
+ * One compiled handle per schema object, so repeated validation
+ * (e.g. on every keystroke) skips re-parsing the schema
+ * Builders change in place, so an entry is only reused while the schema
+ * still serializes to the same JSON
+ */
+const compiledSchemas = new WeakMap<object, CompiledEntry>();
+
+/**
+ * Serialize a schema builder or plain schema to JSON
+ */
+function schemaToJson(schema: SchemaBuilder<any> | Schema): string {
+  return JSON.stringify(
+    schema instanceof Object && "toJSON" in schema ? schema.toJSON() : schema,
+  );
+}
+
+/**
+ * Get the cached compiled handle for a schema, compiling it on first use
+ * and again whenever its JSON has changed since
+ * Returns null for schemas that fail to compile, so callers fall back to
+ * the stateless API which reports the problem as a validation error
+ */
+function getCompiled(
+  wasm: WasmModule,
+  schema: SchemaBuilder<any> | Schema,
+  schemaJson: string,
+): CompiledSchemaHandle | null {
+  const cached = compiledSchemas.get(schema);
+  if (cached?.json === schemaJson) {
+    return cached.handle;
+  }
+
+  Validator.release(schema);
+  let handle: CompiledSchemaHandle;
+  try {
+    handle = new wasm.CompiledSchema(schemaJson);
+  } catch {
+    return null;
+  }
+  compiledSchemas.set(schema, { json: schemaJson, handle });
+  return handle;
 }
 
 /**
@@ -111,17 +183,16 @@ export class Validator {
     value: unknown,
   ): Promise<ValidationResult> {
     const wasm = await getWasm();
-
-    // Serialize schema to JSON
-    const schemaJson = JSON.stringify(
-      schema instanceof Object && "toJSON" in schema ? schema.toJSON() : schema,
-    );
+    const schemaJson = schemaToJson(schema);
+    const compiled = getCompiled(wasm, schema, schemaJson);
 
     // Serialize value to JSON
     const valueJson = JSON.stringify(value);
 
     // Call WASM validator (single call, zero-copy)
-    const resultJson = wasm.WasmValidator.validate(schemaJson, valueJson);
+    const resultJson = compiled
+      ? compiled.validate(valueJson)
+      : wasm.WasmValidator.validate(schemaJson, valueJson);
 
     // Parse result
     return JSON.parse(resultJson) as ValidationResult;
@@ -143,40 +214,153 @@ export class Validator {
     path: string[],
   ): Promise<ValidationResult> {
     const wasm = await getWasm();
+    const schemaJson = schemaToJson(schema);
+    const compiled = getCompiled(wasm, schema, schemaJson);
 
     // Serialize inputs
-    const schemaJson = JSON.stringify(
-      schema instanceof Object && "toJSON" in schema ? schema.toJSON() : schema,
-    );
     const valueJson = JSON.stringify(value);
     const pathJson = JSON.stringify(path);
 
     // Call WASM validator (single call)
-    const resultJson = wasm.WasmValidator.validate_at_path(
-      schemaJson,
-      valueJson,
-      pathJson,
-    );
+    const resultJson = compiled
+      ? compiled.validate_at_path(valueJson, pathJson)
+      : wasm.WasmValidator.validate_at_path(
+          schemaJson,
+          valueJson,
+          pathJson,
+        );
 
     // Parse result
     return JSON.parse(resultJson) as ValidationResult;
   }
 
   /**
-   * Validate and throw on error (for convenience)
+   * Select the locale used for default error messages
+   * Regional locales fall back to their language, then to English
+   *
+   * @param locale - Locale tag (e.g., 'de' or 'fr-CA')
+   */
+  static async setLocale(locale: string): Promise<void> {
+    const wasm = await getWasm();
+    wasm.WasmValidator.set_locale(locale);
+  }
+
+  /**
+   * Convert string input on every node that does not set coerce itself
+   *
+   * @param enabled - Whether coercion is on by default
+   */
+  static async setCoerce(enabled: boolean): Promise<void> {
+    const wasm = await getWasm();
+    wasm.WasmValidator.set_coerce(enabled);
+  }
+
+  /**
+   * Fix the time relative date bounds and ages are measured from
+   *
+   * @param now - Reference time, or null to use the current time
+   */
+  static async setNow(now: Date | number | null): Promise<void> {
+    const wasm = await getWasm();
+    wasm.WasmValidator.set_now(now === null ? undefined : Number(now));
+  }
+
+  /**
+   * Limit how many references may expand inside one another
+   * Deeper input under a recursive schema fails with too_deep
+   *
+   * @param depth - Maximum nesting of r.ref() expansions (default: 32)
+   */
+  static async setMaxDepth(depth: number): Promise<void> {
+    const wasm = await getWasm();
+    wasm.WasmValidator.set_max_depth(depth);
+  }
+
+  /**
+   * Register or extend the message catalog for a locale
+   * Templates use the same placeholders as custom messages
+   *
+   * @param locale - Locale tag
+   * @param catalog - Message templates keyed by error code
+   */
+  static async registerLocale(
+    locale: string,
+    catalog: Record<string, string>,
+  ): Promise<void> {
+    const wasm = await getWasm();
+    const result = JSON.parse(
+      wasm.WasmValidator.register_locale(locale, JSON.stringify(catalog)),
+    ) as ValidationResult;
+
+    if (!result.success) {
+      throw new ValidationException(result.errors || []);
+    }
+  }
+
+  /**
+   * Apply the combinators of a composed schema (pick, omit, extend, ...)
+   * and return the plain schema the validator sees
+   *
+   * @param schema - Schema builder or schema JSON
+   */
+  static async expandSchema(schema: SchemaBuilder<any> | Schema): Promise<Schema> {
+    const wasm = await getWasm();
+    const result = JSON.parse(
+      wasm.WasmValidator.expand_schema(schemaToJson(schema)),
+    ) as ParseResult<Schema>;
+
+    if (!result.success) {
+      throw new ValidationException(result.errors || []);
+    }
+    return result.data as Schema;
+  }
+
+  /**
+   * Free the compiled WASM handle cached for a schema
+   * Call when the schema is discarded; a schema changed in place is
+   * recompiled automatically on its next use
+   */
+  static release(schema: SchemaBuilder<any> | Schema): void {
+    compiledSchemas.get(schema)?.handle.free();
+    compiledSchemas.delete(schema);
+  }
+
+  /**
+   * Validate and return the cleaned output
+   * Defaults are applied and unknown object keys removed
+   * Auto-initializes WASM on first use
+   */
+  static async parseResult<T>(
+    schema: SchemaBuilder<T> | Schema,
+    value: unknown,
+  ): Promise<ParseResult<T>> {
+    const wasm = await getWasm();
+    const schemaJson = schemaToJson(schema);
+    const compiled = getCompiled(wasm, schema, schemaJson);
+
+    const valueJson = JSON.stringify(value);
+    const resultJson = compiled
+      ? compiled.parse(valueJson)
+      : wasm.WasmValidator.parse(schemaJson, valueJson);
+
+    return JSON.parse(resultJson) as ParseResult<T>;
+  }
+
+  /**
+   * Parse and throw on error (for convenience)
    * Auto-initializes WASM on first use
    */
   static async parse<T>(
     schema: SchemaBuilder<T> | Schema,
     value: unknown,
   ): Promise<T> {
-    const result = await this.validate(schema, value);
+    const result = await this.parseResult(schema, value);
 
     if (!result.success) {
       throw new ValidationException(result.errors || []);
     }
 
-    return value as T;
+    return result.data as T;
   }
 
   /**
@@ -189,10 +373,10 @@ export class Validator {
   ): Promise<
     { success: true; data: T } | { success: false; errors: ValidationError[] }
   > {
-    const result = await this.validate(schema, value);
+    const result = await this.parseResult(schema, value);
 
     if (result.success) {
-      return { success: true, data: value as T };
+      return { success: true, data: result.data as T };
     } else {
       return { success: false, errors: result.errors || [] };
     }
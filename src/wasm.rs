@@ -1,14 +1,24 @@
 use wasm_bindgen::prelude::*;
-use crate::schema::Schema;
-use crate::validator::Validator;
+use crate::locale::{self, Catalog};
+use crate::lookup::Lookups;
+use crate::pattern::PatternCache;
+use crate::schema::{Schema, ValidationError};
+use crate::validator::{ValidationOptions, Validator};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Options selected from JavaScript, applied to every validation call
+    static OPTIONS: RefCell<ValidationOptions> = RefCell::new(ValidationOptions::default());
+}
 
 /// WASM interface for validation
 /// 
-/// This module exposes exactly two functions to JavaScript:
+/// This module exposes these validation functions to JavaScript:
 /// - validate: validates entire data against schema
 /// - validate_at_path: validates data at a specific path
+/// - parse: validates data and returns the cleaned output
 /// 
-/// Both functions accept and return JSON strings for zero-copy performance
+/// They accept and return JSON strings for zero-copy performance
 #[wasm_bindgen]
 pub struct WasmValidator;
 
@@ -26,21 +36,11 @@ impl WasmValidator {
     /// - Error: `{"success": false, "errors": [...]}`
     #[wasm_bindgen]
     pub fn validate(schema_json: &str, value_json: &str) -> String {
-        match Self::validate_internal(schema_json, value_json) {
-            Ok(_) => {
-                serde_json::json!({
-                    "success": true
-                })
-                .to_string()
-            }
-            Err(errors) => {
-                serde_json::json!({
-                    "success": false,
-                    "errors": errors
-                })
-                .to_string()
-            }
-        }
+        let result = parse_json::<Schema>(schema_json, "schema").and_then(|schema| {
+            let value = parse_json(value_json, "value")?;
+            with_options(|options| Validator::validate_with_options(&schema, &value, options))
+        });
+        result_json(result)
     }
 
     /// Validate data at a specific path in the schema
@@ -54,90 +54,231 @@ impl WasmValidator {
     /// JSON string with validation result (same format as validate)
     #[wasm_bindgen]
     pub fn validate_at_path(schema_json: &str, value_json: &str, path_json: &str) -> String {
-        match Self::validate_at_path_internal(schema_json, value_json, path_json) {
-            Ok(_) => {
-                serde_json::json!({
-                    "success": true
-                })
-                .to_string()
-            }
-            Err(errors) => {
-                serde_json::json!({
-                    "success": false,
-                    "errors": errors
-                })
-                .to_string()
-            }
-        }
+        let result = parse_json::<Schema>(schema_json, "schema").and_then(|schema| {
+            let value = parse_json(value_json, "value")?;
+            let path: Vec<String> = parse_json(path_json, "path")?;
+            with_options(|options| {
+                Validator::validate_at_path_with_options(&schema, &value, &path, options)
+            })
+        });
+        result_json(result)
     }
 
-    /// Internal validation logic
-    fn validate_internal(
-        schema_json: &str,
-        value_json: &str,
-    ) -> Result<(), Vec<crate::schema::ValidationError>> {
-        // Parse schema
-        let schema: Schema = serde_json::from_str(schema_json)
-            .map_err(|e| {
-                vec![crate::schema::ValidationError::new(
-                    vec![],
-                    "parse_error",
-                    format!("Invalid schema JSON: {}", e),
-                )]
-            })?;
-
-        // Parse value
-        let value: serde_json::Value = serde_json::from_str(value_json)
-            .map_err(|e| {
-                vec![crate::schema::ValidationError::new(
-                    vec![],
-                    "parse_error",
-                    format!("Invalid value JSON: {}", e),
-                )]
-            })?;
-
-        // Validate
-        Validator::validate(&schema, &value)
-    }
-
-    /// Internal path validation logic
-    fn validate_at_path_internal(
-        schema_json: &str,
-        value_json: &str,
-        path_json: &str,
-    ) -> Result<(), Vec<crate::schema::ValidationError>> {
-        // Parse schema
+    /// Validate data and return the cleaned output
+    ///
+    /// # Arguments
+    /// * `schema_json` - JSON string representing the schema AST
+    /// * `value_json` - JSON string representing the data to parse
+    ///
+    /// # Returns
+    /// JSON string with parse result:
+    /// - Success: `{"success": true, "data": ...}` with defaults applied and unknown keys removed
+    /// - Error: `{"success": false, "errors": [...]}`
+    #[wasm_bindgen]
+    pub fn parse(schema_json: &str, value_json: &str) -> String {
+        let result = parse_json::<Schema>(schema_json, "schema").and_then(|schema| {
+            let value = parse_json(value_json, "value")?;
+            with_options(|options| Validator::parse_with_options(&schema, &value, options))
+        });
+        parse_result_json(result)
+    }
+
+    /// Apply the `compose` nodes in a schema and return the plain schema
+    ///
+    /// # Arguments
+    /// * `schema_json` - JSON string representing the schema AST
+    ///
+    /// # Returns
+    /// JSON string with the expanded schema:
+    /// - Success: `{"success": true, "data": ...}` with extend, merge, pick, omit, partial,
+    ///   deep_partial and required applied
+    /// - Error: `{"success": false, "errors": [...]}`, e.g. for `pick` on a non-object schema
+    #[wasm_bindgen]
+    pub fn expand_schema(schema_json: &str) -> String {
+        let result = parse_json::<Schema>(schema_json, "schema").and_then(|schema| {
+            serde_json::to_value(schema).map_err(|e| {
+                vec![ValidationError::new(vec![], "invalid_schema", e.to_string())]
+            })
+        });
+        parse_result_json(result)
+    }
+
+    /// Select the locale used for default error messages
+    ///
+    /// Unknown locales fall back to their language, then to English.
+    #[wasm_bindgen]
+    pub fn set_locale(locale: &str) {
+        OPTIONS.with(|options| options.borrow_mut().locale = locale.to_string());
+    }
+
+    /// Convert string input on every node that does not set `coerce` itself
+    #[wasm_bindgen]
+    pub fn set_coerce(enabled: bool) {
+        OPTIONS.with(|options| options.borrow_mut().coerce = enabled);
+    }
+
+    /// Fix the time relative date bounds and ages are measured from
+    ///
+    /// # Arguments
+    /// * `millis` - Milliseconds since the Unix epoch, or `None` for the current time
+    #[wasm_bindgen]
+    pub fn set_now(millis: Option<f64>) {
+        OPTIONS.with(|options| options.borrow_mut().now = millis.map(|millis| millis as i64));
+    }
+
+    /// Limit how many references may expand inside one another
+    ///
+    /// Input nested deeper than this under a recursive schema fails with `too_deep`.
+    #[wasm_bindgen]
+    pub fn set_max_depth(depth: usize) {
+        OPTIONS.with(|options| options.borrow_mut().max_depth = depth);
+    }
+
+    /// Register or extend the message catalog for a locale
+    ///
+    /// # Arguments
+    /// * `locale` - Locale tag such as `"de"` or `"pt-BR"`
+    /// * `catalog_json` - JSON object mapping error codes to message templates
+    ///
+    /// # Returns
+    /// `{"success": true}`, or a `parse_error` result if the catalog is malformed
+    #[wasm_bindgen]
+    pub fn register_locale(locale: &str, catalog_json: &str) -> String {
+        let result = parse_json::<Catalog>(catalog_json, "catalog")
+            .map(|catalog| locale::register_catalog(locale, catalog));
+        result_json(result)
+    }
+
+}
+
+/// Schema parsed and prepared once, for repeated validation from JavaScript
+///
+/// Holds the deserialized schema tree, its compiled patterns and lookup
+/// tables (the option for each discriminator tag, the definition each
+/// reference resolves to), so each call only has to parse the value. The
+/// handle owns WASM memory: call `free()` once the schema is no longer used.
+#[wasm_bindgen]
+pub struct CompiledSchema {
+    /// Boxed so the tree keeps the address the lookups were built for
+    schema: Box<Schema>,
+    patterns: PatternCache,
+    lookups: Lookups,
+}
+
+#[wasm_bindgen]
+impl CompiledSchema {
+    /// Parse and compile a schema
+    ///
+    /// Throws if the schema JSON is malformed, contains an invalid pattern or
+    /// a reference that does not resolve
+    #[wasm_bindgen(constructor)]
+    pub fn new(schema_json: &str) -> Result<CompiledSchema, String> {
         let schema: Schema = serde_json::from_str(schema_json)
-            .map_err(|e| {
-                vec![crate::schema::ValidationError::new(
-                    vec![],
-                    "parse_error",
-                    format!("Invalid schema JSON: {}", e),
-                )]
-            })?;
-
-        // Parse value
-        let value: serde_json::Value = serde_json::from_str(value_json)
-            .map_err(|e| {
-                vec![crate::schema::ValidationError::new(
-                    vec![],
-                    "parse_error",
-                    format!("Invalid value JSON: {}", e),
-                )]
-            })?;
-
-        // Parse path
-        let path: Vec<String> = serde_json::from_str(path_json)
-            .map_err(|e| {
-                vec![crate::schema::ValidationError::new(
-                    vec![],
-                    "parse_error",
-                    format!("Invalid path JSON: {}", e),
-                )]
-            })?;
-
-        // Validate at path
-        Validator::validate_at_path(&schema, &value, &path)
+            .map_err(|e| format!("Invalid schema JSON: {}", e))?;
+
+        let patterns = PatternCache::default();
+        patterns.compile_schema(&schema).map_err(|e| e.to_string())?;
+
+        let schema = Box::new(schema);
+        let lookups = Lookups::build(&schema);
+        Ok(Self { schema, patterns, lookups })
+    }
+
+    /// Validate data against the compiled schema
+    ///
+    /// Returns the same JSON result as `WasmValidator.validate`
+    #[wasm_bindgen]
+    pub fn validate(&self, value_json: &str) -> String {
+        let result = parse_json(value_json, "value").and_then(|value| {
+            with_options(|options| {
+                Validator::validate_with_patterns(&self.schema, &value, &self.patterns, Some(&self.lookups), options)
+            })
+        });
+        result_json(result)
+    }
+
+    /// Parse data with the compiled schema
+    ///
+    /// Returns the same JSON result as `WasmValidator.parse`
+    #[wasm_bindgen]
+    pub fn parse(&self, value_json: &str) -> String {
+        let result = parse_json(value_json, "value").and_then(|value| {
+            with_options(|options| {
+                Validator::parse_with_patterns(&self.schema, &value, &self.patterns, Some(&self.lookups), options)
+            })
+        });
+        parse_result_json(result)
+    }
+
+    /// Validate data at a specific path in the compiled schema
+    ///
+    /// Returns the same JSON result as `WasmValidator.validate_at_path`
+    #[wasm_bindgen]
+    pub fn validate_at_path(&self, value_json: &str, path_json: &str) -> String {
+        let result = parse_json(value_json, "value").and_then(|value| {
+            let path: Vec<String> = parse_json(path_json, "path")?;
+            with_options(|options| {
+                Validator::validate_at_path_with_patterns(
+                    &self.schema,
+                    &value,
+                    &path,
+                    &self.patterns,
+                    Some(&self.lookups),
+                    options,
+                )
+            })
+        });
+        result_json(result)
+    }
+}
+
+/// Run with the options selected from JavaScript
+fn with_options<R>(f: impl FnOnce(&ValidationOptions) -> R) -> R {
+    OPTIONS.with(|options| f(&options.borrow()))
+}
+
+/// Deserialize a JSON argument, reporting failures as a `parse_error`
+fn parse_json<T: serde::de::DeserializeOwned>(
+    json: &str,
+    what: &str,
+) -> Result<T, Vec<ValidationError>> {
+    serde_json::from_str(json).map_err(|e| {
+        vec![ValidationError::new(
+            vec![],
+            "parse_error",
+            format!("Invalid {} JSON: {}", what, e),
+        )]
+    })
+}
+
+/// Serialize a validation result to the JSON shape returned to JavaScript
+fn result_json(result: Result<(), Vec<ValidationError>>) -> String {
+    match result {
+        Ok(_) => serde_json::json!({
+            "success": true
+        })
+        .to_string(),
+        Err(errors) => serde_json::json!({
+            "success": false,
+            "errors": errors
+        })
+        .to_string(),
+    }
+}
+
+/// Serialize a parse result, carrying the output value on success
+fn parse_result_json(result: Result<serde_json::Value, Vec<ValidationError>>) -> String {
+    match result {
+        Ok(data) => serde_json::json!({
+            "success": true,
+            "data": data
+        })
+        .to_string(),
+        Err(errors) => serde_json::json!({
+            "success": false,
+            "errors": errors
+        })
+        .to_string(),
     }
 }
 
@@ -166,6 +307,8 @@ mod tests {
         
         assert_eq!(parsed["success"], false);
         assert!(parsed["errors"].is_array());
+        assert_eq!(parsed["errors"][0]["params"]["minimum"], 5);
+        assert_eq!(parsed["errors"][0]["params"]["actual"], 2);
     }
 
     #[test]
@@ -179,4 +322,181 @@ mod tests {
         
         assert_eq!(parsed["success"], true);
     }
+
+    #[test]
+    fn test_compiled_schema() {
+        let schema = r#"{"type":"object","shape":{"zip":{"type":"string","pattern":"^\\d{5}$"}}}"#;
+        let compiled = CompiledSchema::new(schema).unwrap();
+
+        // Without the `regex` feature the pattern is matched as a plain substring
+        if cfg!(feature = "regex") {
+            let parsed: serde_json::Value =
+                serde_json::from_str(&compiled.validate(r#"{"zip":"12345"}"#)).unwrap();
+            assert_eq!(parsed["success"], true);
+
+            let parsed: serde_json::Value =
+                serde_json::from_str(&compiled.validate_at_path(r#"{"zip":"1234"}"#, r#"["zip"]"#)).unwrap();
+            assert_eq!(parsed["success"], false);
+            assert_eq!(parsed["errors"][0]["path"][0], "zip");
+        }
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&compiled.validate("not json")).unwrap();
+        assert_eq!(parsed["errors"][0]["code"], "parse_error");
+    }
+
+    #[test]
+    fn test_compiled_schema_lookups() {
+        // Options behind references, a repeated tag and a numeric tag written two ways
+        let schema = r#"{
+            "type": "object",
+            "shape": {"payment": {"type": "discriminated_union", "discriminator": "kind", "options": [
+                {"type": "ref", "name": "Card"},
+                {"type": "object", "shape": {"kind": {"type": "literal", "value": "card"}, "other": {"type": "string"}}},
+                {"type": "object", "shape": {"kind": {"type": "literal", "value": 2}, "iban": {"type": "string"}}}
+            ]}},
+            "definitions": {
+                "Card": {"type": "ref", "name": "CardFields"},
+                "CardFields": {"type": "object", "shape": {"kind": {"type": "literal", "value": "card"}, "number": {"type": "string"}}}
+            }
+        }"#;
+        let compiled = CompiledSchema::new(schema).unwrap();
+        let both = |value: &str| {
+            let compiled: serde_json::Value = serde_json::from_str(&compiled.validate(value)).unwrap();
+            let stateless: serde_json::Value =
+                serde_json::from_str(&WasmValidator::validate(schema, value)).unwrap();
+            assert_eq!(compiled, stateless, "{}", value);
+            compiled
+        };
+
+        assert_eq!(both(r#"{"payment": {"kind": "card", "number": "4242"}}"#)["success"], true);
+        let parsed = both(r#"{"payment": {"kind": "card", "other": "x"}}"#);
+        assert_eq!(parsed["errors"][0]["path"], serde_json::json!(["payment", "number"]));
+        assert_eq!(both(r#"{"payment": {"kind": 2.0, "iban": "DE00"}}"#)["success"], true);
+        let parsed = both(r#"{"payment": {"kind": "cash"}}"#);
+        assert_eq!(parsed["errors"][0]["code"], "invalid_union_discriminator");
+
+        let parsed: serde_json::Value = serde_json::from_str(&compiled.validate_at_path(
+            r#"{"payment": {"kind": 2, "iban": 1}}"#,
+            r#"["payment", "iban"]"#,
+        ))
+        .unwrap();
+        assert_eq!(parsed["errors"][0]["code"], "invalid_type");
+    }
+
+    #[test]
+    fn test_compiled_schema_rejects_invalid_schema() {
+        assert!(CompiledSchema::new(r#"{"type":"unknown"}"#).is_err());
+
+        if cfg!(feature = "regex") {
+            let error = CompiledSchema::new(r#"{"type":"string","pattern":"(unclosed"}"#)
+                .err()
+                .unwrap();
+            assert!(error.starts_with("Invalid pattern '(unclosed'"));
+        }
+
+        let error = CompiledSchema::new(
+            r#"{"type":"object","shape":{"parent":{"type":"ref","name":"Node"}}}"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error, "Unknown schema reference 'Node' at 'parent'");
+
+        let error = CompiledSchema::new(
+            r#"{"type":"ref","name":"A","definitions":{"A":{"type":"ref","name":"B"},"B":{"type":"ref","name":"A"}}}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(error.starts_with("Schema reference 'A' at ''"));
+    }
+
+    #[test]
+    fn test_wasm_locale() {
+        let result = WasmValidator::register_locale("x-wasm", r#"{"string.min":"Zu kurz"}"#);
+        assert_eq!(result, r#"{"success":true}"#);
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&WasmValidator::register_locale("x-wasm", "[")).unwrap();
+        assert_eq!(parsed["errors"][0]["code"], "parse_error");
+
+        WasmValidator::set_locale("x-wasm");
+        let result = WasmValidator::validate(r#"{"type":"string","min":5}"#, r#""hi""#);
+        WasmValidator::set_locale("en");
+
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["errors"][0]["message"], "Zu kurz");
+    }
+
+    #[test]
+    fn test_wasm_parse() {
+        let schema = r#"{"type":"object","shape":{"name":{"type":"string"},"role":{"type":"string","default":"member"}}}"#;
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&WasmValidator::parse(schema, r#"{"name":"Ada","extra":1}"#)).unwrap();
+        assert_eq!(parsed["success"], true);
+        assert_eq!(parsed["data"], serde_json::json!({"name": "Ada", "role": "member"}));
+
+        let compiled = CompiledSchema::new(schema).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&compiled.parse(r#"{"name":1}"#)).unwrap();
+        assert_eq!(parsed["success"], false);
+        assert!(parsed.get("data").is_none());
+        assert_eq!(parsed["errors"][0]["path"][0], "name");
+    }
+
+    #[test]
+    fn test_wasm_now() {
+        let schema = r#"{"type":"date","min_age":18}"#;
+
+        // 2024-06-15T12:00:00Z
+        WasmValidator::set_now(Some(1_718_452_800_000.0));
+        let adult = WasmValidator::validate(schema, r#""2006-06-15""#);
+        let minor = WasmValidator::validate(schema, r#""2006-06-16""#);
+        WasmValidator::set_now(None);
+
+        assert!(adult.contains("\"success\":true"));
+        assert!(minor.contains("date.min_age"));
+    }
+
+    #[test]
+    fn test_wasm_expand_schema() {
+        let user = r#"{"type":"object","shape":{"id":{"type":"string"},"name":{"type":"string"},"password":{"type":"string"}}}"#;
+        let update = format!(
+            r#"{{"type":"compose","schema":{},"steps":[{{"op":"omit","keys":["id","password"]}},{{"op":"partial"}}]}}"#,
+            user
+        );
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&WasmValidator::expand_schema(&update)).unwrap();
+        assert_eq!(
+            parsed["data"],
+            serde_json::json!({"type": "object", "shape": {"name": {"type": "string", "optional": true}}})
+        );
+
+        let result = WasmValidator::validate(&update, r#"{}"#);
+        assert!(result.contains("\"success\":true"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&WasmValidator::expand_schema(
+            r#"{"type":"compose","schema":{"type":"string"},"steps":[{"op":"pick","keys":["a"]}]}"#,
+        ))
+        .unwrap();
+        assert_eq!(parsed["errors"][0]["code"], "parse_error");
+        assert!(parsed["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("'pick' requires an object schema, found string"));
+    }
+
+    #[test]
+    fn test_wasm_max_depth() {
+        let schema = r#"{"type":"ref","name":"List","definitions":{"List":{"type":"object","shape":{"next":{"type":"ref","name":"List","nullable":true}}}}}"#;
+        let value = r#"{"next":{"next":{"next":null}}}"#;
+
+        WasmValidator::set_max_depth(2);
+        let shallow = WasmValidator::validate(schema, value);
+        WasmValidator::set_max_depth(32);
+        let deep = WasmValidator::validate(schema, value);
+
+        assert!(shallow.contains("too_deep"));
+        assert!(deep.contains("\"success\":true"));
+    }
 }
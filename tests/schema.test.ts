@@ -206,5 +206,28 @@ describe("Schema Builders", () => {
       assert.strictEqual((value as any).age, 30);
       assert.strictEqual((value as any).active, true);
     });
+
+    it("should widen optional, nullable and defaulted fields", () => {
+      const schema = r.object({
+        name: r.string(),
+        nickname: r.string().min(2).optional(),
+        deletedAt: r.string().nullable(),
+        role: r.string().optional().default("user"),
+      });
+      type Inferred = Infer<typeof schema>;
+      const value: Inferred = { name: "Jo", deletedAt: null, role: "admin" };
+      const nickname: Inferred["nickname"] = undefined;
+      // @ts-expect-error a default fills the field, so it is never undefined
+      const role: Inferred["role"] = undefined;
+      assert.strictEqual(value.nickname, nickname);
+      assert.strictEqual(role, undefined);
+
+      const json = schema.toJSON();
+      assert.strictEqual((json.shape.nickname as any).min, 2);
+      assert.strictEqual(json.shape.nickname.optional, true);
+      assert.strictEqual(json.shape.deletedAt.nullable, true);
+      assert.strictEqual(json.shape.role.optional, true);
+      assert.strictEqual(json.shape.role.default, "user");
+    });
   });
 });
@@ -265,6 +265,197 @@ describe("Path Validation", () => {
   });
 });
 
+describe("Optional, Nullable and Default Fields", () => {
+  const schema = r.object({
+    name: r.string(),
+    nickname: r.string().min(2).optional(),
+    deletedAt: r.string().nullable(),
+    role: r.string().default("user"),
+  });
+
+  test("accepts missing optional fields and null for nullable fields", async () => {
+    const result = await Validator.validate(schema, { name: "Jo", deletedAt: null });
+    assert.strictEqual(result.success, true);
+  });
+
+  test("still checks an optional field that is present", async () => {
+    const result = await Validator.validate(schema, {
+      name: "Jo",
+      nickname: "J",
+      deletedAt: null,
+    });
+    assert.strictEqual(result.success, false);
+    assert.deepStrictEqual(result.errors?.[0].path, ["nickname"]);
+  });
+
+  test("reports missing required and non-nullable fields", async () => {
+    const result = await Validator.validate(schema, { deletedAt: "2024-01-01" });
+    assert.strictEqual(result.success, false);
+    assert.deepStrictEqual(result.errors?.map((e) => e.code), ["required"]);
+
+    const nulls = await Validator.validate(schema, { name: null, deletedAt: null });
+    assert.strictEqual(nulls.success, false);
+    assert.strictEqual(nulls.errors?.[0].code, "invalid_type");
+  });
+
+  test("parse() fills in defaults", async () => {
+    const data = await Validator.parse(schema, { name: "Jo", deletedAt: null });
+    assert.deepStrictEqual(data, { name: "Jo", deletedAt: null, role: "user" });
+  });
+});
+
+describe("Object Composition", () => {
+  const user = r.object({
+    id: r.number(),
+    name: r.string(),
+    email: r.string().email(),
+  });
+
+  test("builds compose nodes", () => {
+    const json = user.pick(["id", "name"]).partial().toJSON();
+    assert.strictEqual(json.type, "compose");
+    assert.deepStrictEqual(json.steps, [{ op: "partial" }]);
+    assert.strictEqual(json.schema.type, "compose");
+    assert.deepStrictEqual((json.schema as any).steps, [{ op: "pick", keys: ["id", "name"] }]);
+  });
+
+  test("expandSchema() applies the combinators", async () => {
+    const expanded = (await Validator.expandSchema(
+      user.omit(["email"]).extend({ role: r.string() }),
+    )) as any;
+    assert.strictEqual(expanded.type, "object");
+    assert.deepStrictEqual(Object.keys(expanded.shape), ["id", "name", "role"]);
+
+    const partial = (await Validator.expandSchema(user.pick(["name"]).partial())) as any;
+    assert.deepStrictEqual(Object.keys(partial.shape), ["name"]);
+    assert.strictEqual(partial.shape.name.optional, true);
+  });
+
+  test("expandSchema() rejects combinators on non-object schemas", async () => {
+    await assert.rejects(
+      Validator.expandSchema({ type: "compose", schema: { type: "string" }, steps: [{ op: "partial" }] }),
+    );
+  });
+
+  test("validates against the composed schema", async () => {
+    const merged = user.merge(r.object({ name: r.string().min(3) }).strict());
+    const data = { id: 1, name: "Jo", email: "jo@example.com", extra: true };
+    const result = await Validator.validate(merged, data);
+    assert.strictEqual(result.success, false);
+    assert.deepStrictEqual(result.errors?.map((e) => e.code).sort(), [
+      "string.min",
+      "unrecognized_keys",
+    ]);
+
+    const patch = await Validator.parse(user.partial(), { name: "Jo" });
+    assert.deepStrictEqual(patch, { name: "Jo" });
+  });
+
+  test("merges object outputs of an intersection", async () => {
+    const schema = r.intersection([user.pick(["id"]), r.object({ tags: r.array(r.string()) })]);
+    const data = await Validator.parse(schema, { id: 1, tags: ["a"], name: "Jo" });
+    assert.deepStrictEqual(data, { id: 1, tags: ["a"] });
+  });
+});
+
+describe("Global Options", () => {
+  test("setLocale() selects the catalog, falling back to the language", async () => {
+    const schema = r.string().min(5);
+    await Validator.setLocale("de-AT");
+    const german = await Validator.validate(schema, "hi");
+    await Validator.setLocale("en");
+    const english = await Validator.validate(schema, "hi");
+
+    assert.strictEqual(german.errors?.[0].message, "Muss mindestens 5 Zeichen lang sein");
+    assert.strictEqual(english.errors?.[0].message, "String must be at least 5 characters");
+  });
+
+  test("registerLocale() adds templates and rejects malformed catalogs", async () => {
+    await Validator.registerLocale("x-test", { "string.min": "Too short: {minimum}" });
+    await Validator.setLocale("x-test");
+    const result = await Validator.validate(r.string().min(5), "hi");
+    await Validator.setLocale("en");
+    assert.strictEqual(result.errors?.[0].message, "Too short: 5");
+
+    await assert.rejects(
+      Validator.registerLocale("x-test", { "string.min": 5 } as unknown as Record<string, string>),
+    );
+  });
+
+  test("setCoerce() converts string input unless a node opts out", async () => {
+    const schema = r.object({ age: r.number(), code: r.number().coerce(false) });
+    await Validator.setCoerce(true);
+    const coerced = await Validator.safeParse(schema, { age: "7", code: 7 });
+    const optedOut = await Validator.validate(schema, { age: "7", code: "7" });
+    await Validator.setCoerce(false);
+    const plain = await Validator.validate(schema, { age: "7", code: 7 });
+
+    assert.deepStrictEqual(coerced, { success: true, data: { age: 7, code: 7 } });
+    assert.deepStrictEqual(optedOut.errors?.map((e) => e.path), [["code"]]);
+    assert.strictEqual(plain.success, false);
+  });
+
+  test("setNow() fixes the time ages are measured from", async () => {
+    const schema = r.date().minAge(18);
+    await Validator.setNow(new Date("2024-06-15T12:00:00Z"));
+    const adult = await Validator.validate(schema, "2006-06-15");
+    const minor = await Validator.validate(schema, "2006-06-16");
+    await Validator.setNow(null);
+
+    assert.strictEqual(adult.success, true);
+    assert.strictEqual(minor.errors?.[0].code, "date.min_age");
+    assert.strictEqual((await Validator.validate(schema, "2006-06-16")).success, true);
+  });
+
+  test("setMaxDepth() limits how deeply references expand", async () => {
+    type Node = { child?: Node };
+    const schema = r
+      .ref<Node>("Node")
+      .definitions({ Node: r.object({ child: r.ref<Node>("Node").optional() }) });
+    const deep = { child: { child: { child: {} } } };
+    await Validator.setMaxDepth(2);
+    const limited = await Validator.validate(schema, deep);
+    const shallow = await Validator.validate(schema, { child: {} });
+    await Validator.setMaxDepth(32);
+
+    assert.strictEqual(limited.errors?.[0].code, "too_deep");
+    assert.deepStrictEqual(limited.errors?.[0].path, ["child", "child"]);
+    assert.strictEqual(shallow.success, true);
+    assert.strictEqual((await Validator.validate(schema, deep)).success, true);
+  });
+});
+
+describe("Compiled Schema Cache", () => {
+  test("picks up builder changes made after the first validation", async () => {
+    const schema = r.object({ name: r.string() });
+    const data = { name: "Jo", extra: true };
+    assert.strictEqual((await Validator.validate(schema, data)).success, true);
+
+    schema.strict();
+    const strict = await Validator.validate(schema, data);
+    assert.strictEqual(strict.success, false);
+    assert(strict.errors?.some((e) => e.code === "unrecognized_keys"));
+
+    const name = r.string();
+    assert.strictEqual((await Validator.validate(name, "Jo")).success, true);
+    name.min(3);
+    assert.strictEqual((await Validator.validate(name, "Jo")).success, false);
+    const atPath = await Validator.validateAtPath(
+      r.object({ name }),
+      { name: "Jo" },
+      ["name"],
+    );
+    assert.strictEqual(atPath.success, false);
+  });
+
+  test("recompiles a released schema on next use", async () => {
+    const schema = r.string().min(2);
+    assert.strictEqual((await Validator.validate(schema, "ab")).success, true);
+    Validator.release(schema);
+    assert.strictEqual((await Validator.validate(schema, "a")).success, false);
+  });
+});
+
 describe("Validator Methods", () => {
   test("parse() returns data on success", async () => {
     const schema = r.string();
@@ -288,6 +479,40 @@ describe("Validator Methods", () => {
     }
   });
 
+  test("parse() returns the cleaned output", async () => {
+    const schema = r.object({
+      email: r.string().trim().toLowerCase(),
+      tags: r.array(r.object({ name: r.string() })),
+    });
+    const data = await Validator.parse(schema, {
+      email: "  Jo@Example.com ",
+      tags: [{ name: "a", color: "red" }],
+      extra: true,
+    });
+    assert.deepStrictEqual(data, { email: "jo@example.com", tags: [{ name: "a" }] });
+  });
+
+  test("safeParse() returns the cleaned output", async () => {
+    const schema = r.object({ name: r.string().trim() }).passthrough();
+    const result = await Validator.safeParse(schema, { name: " Jo ", extra: 1 });
+    assert.strictEqual(result.success, true);
+    if (result.success) {
+      assert.deepStrictEqual(result.data, { name: "Jo", extra: 1 });
+    }
+  });
+
+  test("parseResult() carries data or errors", async () => {
+    const schema = r.object({ age: r.number().min(18) });
+    const ok = await Validator.parseResult(schema, { age: 30, name: "Jo" });
+    assert.strictEqual(ok.success, true);
+    assert.deepStrictEqual(ok.data, { age: 30 });
+
+    const failed = await Validator.parseResult(schema, { age: 12 });
+    assert.strictEqual(failed.success, false);
+    assert.strictEqual(failed.data, undefined);
+    assert.strictEqual(failed.errors?.[0].code, "number.min");
+  });
+
   test("safeParse() returns error result", async () => {
     const schema = r.string().email();
     const result = await Validator.safeParse(schema, "invalid");